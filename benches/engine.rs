@@ -0,0 +1,44 @@
+//! Compares looking a word up with the engine that's cached after the first call against
+//! importing `phonology_engine` and constructing its engine for every word, like the crate used
+//! to.
+//!
+//! ```sh
+//! cargo bench --bench engine
+//! ```
+//!
+//! Needs `phonology_engine`; without it, nothing is measured.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use lithuanian_phonology::{check_environment, get_stress_options};
+use pyo3::prelude::*;
+
+const WORD: &str = "žodis";
+
+fn engine_lookups(c: &mut Criterion) {
+    if let Err(error) = check_environment() {
+        eprintln!("skipping the engine benchmarks: {error}");
+        return;
+    }
+
+    let mut group = c.benchmark_group("engine");
+    group.sample_size(10);
+    group.bench_function("cached", |b| {
+        b.iter(|| get_stress_options(black_box(WORD)).unwrap())
+    });
+    group.bench_function("constructed per word", |b| {
+        b.iter(|| {
+            Python::with_gil(|py| -> PyResult<()> {
+                let phonology = PyModule::import(py, "phonology_engine")?;
+                let engine = phonology.getattr("PhonologyEngine")?.call0()?;
+                let unit = engine.getattr("process")?.call1((black_box(WORD),))?;
+                black_box(unit.getattr("__next__")?.call0()?);
+                Ok(())
+            })
+            .unwrap()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, engine_lookups);
+criterion_main!(benches);
@@ -0,0 +1,91 @@
+use std::{env, fs, path::PathBuf};
+
+#[allow(dead_code)]
+#[path = "src/offline_format.rs"]
+mod offline_format;
+
+use offline_format::StoredOption;
+
+// The case names the table accepts, in the order of the case numbers in the binary format.
+const CASES: [(&str, &str); 7] = [
+    ("nominative", "vardininkas"),
+    ("genitive", "kilmininkas"),
+    ("dative", "naudininkas"),
+    ("accusative", "galininkas"),
+    ("instrumental", "įnagininkas"),
+    ("locative", "vietininkas"),
+    ("vocative", "šauksmininkas"),
+];
+
+// With the `offline` feature, turns the offline stress table from TSV into the binary format that
+// `lookup_offline` reads. The TSV has one stress option per line: the word, the stress type, the
+// stressed letter index, and optionally the case (english or Lithuanian name) and the number
+// (`vienaskaita` or `daugiskaita`). Empty lines and lines starting with `#` are skipped. The table
+// is `data/offline.tsv` unless `LITHUANIAN_PHONOLOGY_OFFLINE_TABLE` names another file.
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/offline_format.rs");
+    if env::var_os("CARGO_FEATURE_OFFLINE").is_none() {
+        return;
+    }
+    println!("cargo:rerun-if-env-changed=LITHUANIAN_PHONOLOGY_OFFLINE_TABLE");
+    let source = match env::var_os("LITHUANIAN_PHONOLOGY_OFFLINE_TABLE") {
+        Some(path) => PathBuf::from(path),
+        None => PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap()).join("data/offline.tsv"),
+    };
+    println!("cargo:rerun-if-changed={}", source.display());
+
+    let tsv = fs::read_to_string(&source)
+        .unwrap_or_else(|e| panic!("can't read offline table {}: {e}", source.display()));
+    let mut entries = Vec::new();
+    for (number, line) in tsv.lines().enumerate() {
+        let line = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let entry = parse_line(line).unwrap_or_else(|| {
+            panic!(
+                "line {} of {} isn't a stress option: {line}",
+                number + 1,
+                source.display()
+            )
+        });
+        entries.push(entry);
+    }
+
+    let out = PathBuf::from(env::var_os("OUT_DIR").unwrap()).join("offline_stress.bin");
+    let table = offline_format::encode(&entries)
+        .unwrap_or_else(|e| panic!("can't write offline table from {}: {e}", source.display()));
+    fs::write(&out, table).unwrap();
+}
+
+fn parse_line(line: &str) -> Option<(String, Vec<StoredOption>)> {
+    let mut fields = line.split('\t');
+    let word = fields.next()?.to_string();
+    let stress_type = fields.next()?.parse().ok()?;
+    let stressed_letter_index = fields.next()?.parse().ok()?;
+    let case = match fields.next().unwrap_or_default().to_lowercase().as_str() {
+        "" => 0,
+        name => {
+            let position = CASES
+                .iter()
+                .position(|&(english, lithuanian)| name == english || name == lithuanian)?;
+            position as u8 + 1
+        }
+    };
+    let number = match fields.next().unwrap_or_default().to_lowercase().as_str() {
+        "" => 0,
+        "vienaskaita" | "singular" => 1,
+        "daugiskaita" | "plural" => 2,
+        _ => return None,
+    };
+    Some((
+        word,
+        vec![StoredOption {
+            stress_type,
+            stressed_letter_index,
+            case,
+            number,
+        }],
+    ))
+}
@@ -0,0 +1,45 @@
+//! Prints a declension table for a noun with all of its accent marks.
+//!
+//! ```sh
+//! cargo run --example declension -- žodis
+//! ```
+
+use lithuanian_phonology::{display_width, get_all_accentuations, get_case_name};
+
+const CASES: [&str; 7] = [
+    "nominative",
+    "genitive",
+    "dative",
+    "accusative",
+    "instrumental",
+    "locative",
+    "vocative",
+];
+
+fn main() {
+    let Some(word) = std::env::args().nth(1) else {
+        eprintln!("usage: declension <noun>");
+        std::process::exit(1);
+    };
+
+    let accentuations = match get_all_accentuations(&word) {
+        Ok(accentuations) => accentuations,
+        Err(e) => {
+            eprintln!("unable to accentuate {word}: {e}");
+            std::process::exit(1);
+        }
+    };
+
+    let case_width = CASES
+        .iter()
+        .map(|c| display_width(get_case_name(c)))
+        .max()
+        .unwrap_or_default();
+
+    for case in CASES {
+        let name = get_case_name(case);
+        let form = accentuations.get(name).map(String::as_str).unwrap_or("—");
+        let padding = " ".repeat(case_width - display_width(name));
+        println!("{name}{padding}  {form}");
+    }
+}
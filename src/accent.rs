@@ -0,0 +1,389 @@
+use pyo3::Python;
+
+use crate::{
+    effective_index, first_option_by_case, get_stress_options_py, normalize_stress_at, syllables,
+    Case, NormalizedStress, Number, PhonologyEngine, PhonologyError, StressOption, StressResolver,
+};
+
+/// How a noun's stress moves between its forms.
+///
+/// In terms of the four traditional accent paradigms (kirčiuotės), nouns of the first paradigm
+/// have a fixed stem, and those of the other three are mobile, moving to the ending in different
+/// forms. No noun has its stress fixed on the ending in every form, but a set of forms can.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Mobility {
+    /// Always on the stem, like `výras`, `výro`, `výrui`.
+    FixedStem,
+    /// Always on the ending.
+    FixedEnding,
+    /// On the stem in some forms and the ending in others, like `kálnas` and `kalnaĩ`.
+    Mobile,
+}
+
+/// Which part of a word its stress is on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum StressedPart {
+    Stem,
+    Ending,
+}
+
+/// Whether an option stresses the ending of a word, taken to be its last syllable, or the stem.
+/// Words with a single syllable are `None`, since there's no telling the stem and ending apart.
+pub(crate) fn stressed_part(word: &str, option: &StressOption) -> Option<StressedPart> {
+    let nuclei = syllables::nuclei(word);
+    if nuclei.len() < 2 {
+        return None;
+    }
+    match syllables::nucleus_ordinal(word, effective_index(word, option))? {
+        ordinal if ordinal == nuclei.len() - 1 => Some(StressedPart::Ending),
+        _ => Some(StressedPart::Stem),
+    }
+}
+
+fn mobility(parts: impl IntoIterator<Item = StressedPart>) -> Option<Mobility> {
+    let (mut stem, mut ending) = (false, false);
+    for part in parts {
+        match part {
+            StressedPart::Stem => stem = true,
+            StressedPart::Ending => ending = true,
+        }
+    }
+    match (stem, ending) {
+        (true, false) => Some(Mobility::FixedStem),
+        (false, true) => Some(Mobility::FixedEnding),
+        (true, true) => Some(Mobility::Mobile),
+        (false, false) => None,
+    }
+}
+
+/// Works out how the stress of a noun moves, from every reading the engine has of the word.
+///
+/// The engine only knows the readings of the form it's given, so this can only see movement
+/// between forms that are spelled the same, like `galvõs` (genitive singular) and `gálvos`
+/// (nominative plural). [`stress_mobility_of_forms`] looks at several forms together, which gives
+/// a much better picture. The ending is taken to be the last syllable, which holds for nearly
+/// every noun ending, and forms of a single syllable are ignored.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::{stress_mobility, Mobility};
+///
+/// assert_eq!(stress_mobility("galvos").unwrap(), Mobility::Mobile);
+/// ```
+pub fn stress_mobility(word: &str) -> Result<Mobility, PhonologyError> {
+    stress_mobility_of_forms(&[word])
+}
+
+/// Like [`stress_mobility`], but looks at every reading of several forms of the same noun, such as
+/// `["kalnas", "kalno", "kalnai", "kalnus"]`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::{stress_mobility_of_forms, Mobility};
+///
+/// let mobility = stress_mobility_of_forms(&["kalnas", "kalnai"]).unwrap();
+/// assert_eq!(mobility, Mobility::Mobile);
+/// ```
+pub fn stress_mobility_of_forms(forms: &[&str]) -> Result<Mobility, PhonologyError> {
+    let mut parts = Vec::new();
+    Python::with_gil(|py| {
+        for &form in forms {
+            for option in get_stress_options_py(py, form)? {
+                parts.extend(stressed_part(form, &option));
+            }
+        }
+        Ok::<_, PhonologyError>(())
+    })?;
+    mobility(parts).ok_or_else(|| PhonologyError::CaseNotFound {
+        word: forms.join(", "),
+        case: None,
+        number: None,
+    })
+}
+
+/// Works out which of the four traditional accent paradigms (kirčiuotės) a noun is in, as the
+/// number 1 to 4 that dictionaries print next to it. `forms` are forms of the noun in any order;
+/// the ones that matter are the nominative and accusative, singular and plural, such as
+/// `["kalnas", "kalną", "kalnai", "kalnus"]`. Forms the engine reads as other cases are ignored.
+///
+/// The paradigm comes from whether those forms are stressed on the stem or the ending:
+///
+/// | nominative plural | accusative plural | paradigm                                   |
+/// |-------------------|-------------------|--------------------------------------------|
+/// | stem              | stem              | 1, or 3 if the nominative singular isn't   |
+/// | stem              | ending            | 2                                          |
+/// | ending            | stem              | 3                                          |
+/// | ending            | ending            | 4                                          |
+///
+/// This is an approximation. Feminine nouns of the fourth paradigm, like `žiemà`, are stressed
+/// like the second in these forms and come out as 2; telling them apart would take the dative
+/// plural. Nouns with irregular stress, and the ending being taken to be the last syllable (see
+/// [`stress_mobility`]), can also throw it off.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::accent_paradigm;
+///
+/// assert_eq!(accent_paradigm(&["vilkas", "vilką", "vilkai", "vilkus"]).unwrap(), 4);
+/// ```
+pub fn accent_paradigm(forms: &[&str]) -> Result<u8, PhonologyError> {
+    let mut readings = Vec::new();
+    Python::with_gil(|py| {
+        for &form in forms {
+            for option in get_stress_options_py(py, form)? {
+                readings.push((form, option));
+            }
+        }
+        Ok::<_, PhonologyError>(())
+    })?;
+    classify_paradigm(&readings).ok_or_else(|| PhonologyError::CaseNotFound {
+        word: forms.join(", "),
+        case: None,
+        number: Some(Number::Plural.lithuanian_name().to_string()),
+    })
+}
+
+fn classify_paradigm(readings: &[(&str, StressOption)]) -> Option<u8> {
+    let part = |case: Case, number: Number| {
+        readings.iter().find_map(|(form, option)| {
+            let matches = option.grammatical_case.as_deref() == Some(case.lithuanian_name())
+                && option.number.as_deref() == Some(number.lithuanian_name());
+            matches.then(|| stressed_part(form, option)).flatten()
+        })
+    };
+    use StressedPart::*;
+    let paradigm = match (
+        part(Case::Nominative, Number::Plural)?,
+        part(Case::Accusative, Number::Plural)?,
+    ) {
+        (Stem, Stem) if part(Case::Nominative, Number::Singular) == Some(Ending) => 3,
+        (Stem, Stem) => 1,
+        (Stem, Ending) => 2,
+        (Ending, Stem) => 3,
+        (Ending, Ending) => 4,
+    };
+    Some(paradigm)
+}
+
+/// Whether two words are stressed the same way in every case, as far as the engine's readings of
+/// each of them go.
+///
+/// Like [`crate::get_all_accentuations`], this takes the engine's first reading of each word in
+/// each case. Two words are the same when:
+///
+/// - they have readings in exactly the same cases
+/// - in each of those cases, the stress is on the same syllable counting from the end of the word,
+///   so `gálvos` doesn't match `galvõs`
+/// - and it is of the same type once normalized (see [`crate::normalize_stress_type`]), so
+///   `gálvos` matches `kárvės` but not `rañkos`
+///
+/// Number, gender and the rest of the readings are ignored, and so are the letters themselves, so
+/// words with different stems and endings can be the same. Since the engine only knows the
+/// readings of the form it's given (see [`stress_mobility`]), this compares those forms, not whole
+/// paradigms. A word with no readings in any case is a [`PhonologyError::CaseNotFound`] error.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::same_paradigm;
+///
+/// // galvõs and gálvos, but rañkos and rañkos
+/// assert!(!same_paradigm("galvos", "rankos").unwrap());
+/// ```
+pub fn same_paradigm(a: &str, b: &str) -> Result<bool, PhonologyError> {
+    same_paradigm_with(&PhonologyEngine, a, b)
+}
+
+/// Like [`same_paradigm`], but gets the stress options from `resolver` instead of the engine.
+pub fn same_paradigm_with(
+    resolver: &impl StressResolver,
+    a: &str,
+    b: &str,
+) -> Result<bool, PhonologyError> {
+    Ok(stress_pattern(resolver, a)? == stress_pattern(resolver, b)?)
+}
+
+/// Where a word is stressed in one case: the syllable counting from the end, and the stress type.
+type CasePattern = (String, Option<usize>, Option<NormalizedStress>);
+
+fn stress_pattern(
+    resolver: &impl StressResolver,
+    word: &str,
+) -> Result<Vec<CasePattern>, PhonologyError> {
+    let options = resolver.stress_options(word)?;
+    let nuclei = syllables::nuclei(word).len();
+    let mut pattern: Vec<CasePattern> = first_option_by_case(&options)
+        .into_iter()
+        .map(|(case, option)| {
+            let index = effective_index(word, option);
+            let from_end = syllables::nucleus_ordinal(word, index).map(|n| nuclei - 1 - n);
+            let stress = normalize_stress_at(word, index, option.stress_type);
+            (case.to_string(), from_end, stress)
+        })
+        .collect();
+    if pattern.is_empty() {
+        return Err(PhonologyError::CaseNotFound {
+            word: word.to_string(),
+            case: None,
+            number: None,
+        });
+    }
+    pattern.sort_by(|x, y| x.0.cmp(&y.0));
+    Ok(pattern)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn part(word: &str, stress_type: u8, stressed_letter_index: usize) -> Option<StressedPart> {
+        let option = StressOption {
+            stress_type,
+            stressed_letter_index,
+            ..Default::default()
+        };
+        stressed_part(word, &option)
+    }
+
+    #[test]
+    fn stem_or_ending() {
+        assert_eq!(part("kalnas", 1, 1), Some(StressedPart::Stem));
+        assert_eq!(part("kalnai", 2, 5), Some(StressedPart::Ending));
+        assert_eq!(part("galvos", 0, 4), Some(StressedPart::Ending));
+        // the stress on the softening i belongs to the ending
+        assert_eq!(part("kelio", 1, 3), Some(StressedPart::Ending));
+        assert_eq!(part("šuo", 1, 1), None);
+    }
+
+    fn paradigm(forms: [(&'static str, u8, usize); 4]) -> Option<u8> {
+        let forms_in = [
+            (Case::Nominative, Number::Singular),
+            (Case::Accusative, Number::Singular),
+            (Case::Nominative, Number::Plural),
+            (Case::Accusative, Number::Plural),
+        ];
+        let readings: Vec<_> = forms
+            .into_iter()
+            .zip(forms_in)
+            .map(
+                |((form, stress_type, stressed_letter_index), (case, number))| {
+                    let option = StressOption {
+                        stress_type,
+                        stressed_letter_index,
+                        grammatical_case: Some(case.lithuanian_name().to_string()),
+                        number: Some(number.lithuanian_name().to_string()),
+                        ..Default::default()
+                    };
+                    (form, option)
+                },
+            )
+            .collect();
+        classify_paradigm(&readings)
+    }
+
+    #[test]
+    fn one_noun_from_each_paradigm() {
+        // výras, výrą, výrai, výrus
+        let vyras = [
+            ("vyras", 1, 1),
+            ("vyrą", 1, 1),
+            ("vyrai", 1, 1),
+            ("vyrus", 1, 1),
+        ];
+        assert_eq!(paradigm(vyras), Some(1));
+        // rãtas, rãtą, rãtai, ratùs
+        let ratas = [
+            ("ratas", 2, 1),
+            ("ratą", 2, 1),
+            ("ratai", 2, 1),
+            ("ratus", 0, 3),
+        ];
+        assert_eq!(paradigm(ratas), Some(2));
+        // kálnas, kálną, kalnaĩ, kálnus
+        let kalnas = [
+            ("kalnas", 1, 1),
+            ("kalną", 1, 1),
+            ("kalnai", 2, 5),
+            ("kalnus", 1, 1),
+        ];
+        assert_eq!(paradigm(kalnas), Some(3));
+        // vil̃kas, vil̃ką, vilkaĩ, vilkùs
+        let vilkas = [
+            ("vilkas", 2, 2),
+            ("vilką", 2, 2),
+            ("vilkai", 2, 5),
+            ("vilkus", 0, 4),
+        ];
+        assert_eq!(paradigm(vilkas), Some(4));
+        // galvà, gálvą, gálvos, gálvas
+        let galva = [
+            ("galva", 0, 4),
+            ("galvą", 1, 1),
+            ("galvos", 1, 1),
+            ("galvas", 1, 1),
+        ];
+        assert_eq!(paradigm(galva), Some(3));
+    }
+
+    #[test]
+    fn paradigm_needs_plural_forms() {
+        assert_eq!(classify_paradigm(&[]), None);
+    }
+
+    #[test]
+    fn mobility_of_parts() {
+        use StressedPart::*;
+        assert_eq!(mobility([Stem, Stem]), Some(Mobility::FixedStem));
+        assert_eq!(mobility([Ending]), Some(Mobility::FixedEnding));
+        assert_eq!(mobility([Stem, Ending, Stem]), Some(Mobility::Mobile));
+        assert_eq!(mobility([]), None);
+    }
+
+    fn cased(case: Case, stress_type: u8, stressed_letter_index: usize) -> StressOption {
+        StressOption {
+            stress_type,
+            stressed_letter_index,
+            grammatical_case: Some(case.lithuanian_name().to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn paradigms(word: &str) -> Result<Vec<StressOption>, PhonologyError> {
+        Ok(match word {
+            // galvõs, gálvos
+            "galvos" => vec![cased(Case::Genitive, 2, 4), cased(Case::Nominative, 1, 1)],
+            // rañkos, rañkos
+            "rankos" => vec![cased(Case::Genitive, 2, 2), cased(Case::Nominative, 2, 2)],
+            // the nominative first, and a later genitive reading that is ignored
+            "zemes" => vec![
+                cased(Case::Nominative, 1, 1),
+                cased(Case::Genitive, 2, 3),
+                cased(Case::Genitive, 1, 1),
+            ],
+            "varnos" => vec![cased(Case::Genitive, 2, 4), cased(Case::Nominative, 1, 1)],
+            "noun" => vec![StressOption::default()],
+            _ => vec![cased(Case::Genitive, 2, 4)],
+        })
+    }
+
+    #[test]
+    fn same_syllables_and_types() {
+        assert!(same_paradigm_with(&paradigms, "galvos", "varnos").unwrap());
+        assert!(same_paradigm_with(&paradigms, "galvos", "zemes").unwrap());
+        assert!(!same_paradigm_with(&paradigms, "galvos", "rankos").unwrap());
+        // only a genitive
+        assert!(!same_paradigm_with(&paradigms, "galvos", "gerves").unwrap());
+    }
+
+    #[test]
+    fn no_cases() {
+        assert!(matches!(
+            same_paradigm_with(&paradigms, "noun", "galvos"),
+            Err(PhonologyError::CaseNotFound { word, .. }) if word == "noun"
+        ));
+    }
+}
@@ -0,0 +1,309 @@
+use crate::{
+    try_stress_with_option, Case, Number, PhonologyEngine, PhonologyError, StressResolver,
+};
+
+/// Grammatical gender.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Gender {
+    Masculine,
+    Feminine,
+    Neuter,
+}
+
+impl Gender {
+    /// Whether a gender name from the engine, such as `vyriškoji` or `vyr. g.`, is this gender.
+    pub fn matches(self, name: &str) -> bool {
+        let prefix = match self {
+            Gender::Masculine => "vyr",
+            Gender::Feminine => "mot",
+            Gender::Neuter => "bev",
+        };
+        name.to_lowercase().starts_with(prefix)
+    }
+}
+
+/// Degree of comparison of an adjective.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Degree {
+    /// `geras`
+    Positive,
+    /// `geresnis`
+    Comparative,
+    /// `geriausias`
+    Superlative,
+}
+
+/// The endings that can follow `-esn-` and `-iaus-`, in both genders and numbers and in the
+/// definite (pronominal) forms, like `-is` in `geresnis` or `-iosios` in `geriausiosios`.
+const DEGREE_ENDINGS: [&str; 55] = [
+    "is",
+    "io",
+    "iam",
+    "į",
+    "iu",
+    "iame",
+    "i",
+    "ių",
+    "iems",
+    "ius",
+    "iais",
+    "iuose",
+    "ė",
+    "ės",
+    "ei",
+    "ę",
+    "e",
+    "ėje",
+    "ėms",
+    "es",
+    "ėmis",
+    "ėse",
+    "ias",
+    "ią",
+    "ia",
+    "ios",
+    "iai",
+    "ioje",
+    "ioms",
+    "iomis",
+    "iose",
+    "ysis",
+    "iasis",
+    "iojo",
+    "iajam",
+    "įjį",
+    "iąjį",
+    "iuoju",
+    "iajame",
+    "ieji",
+    "iųjų",
+    "iesiems",
+    "iuosius",
+    "iaisiais",
+    "iuosiuose",
+    "ioji",
+    "iosios",
+    "iajai",
+    "iąją",
+    "iąja",
+    "iojoje",
+    "iosioms",
+    "iąsias",
+    "iosiomis",
+    "iosiose",
+];
+
+impl Degree {
+    /// Works out the degree of an adjective form from its suffix: `-esn-` for the comparative and
+    /// `-iaus-` for the superlative. The suffix only counts if it's followed by an adjective
+    /// ending, so `griausmingas` and `nesnaudus` are positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lithuanian_phonology::Degree;
+    ///
+    /// assert_eq!(Degree::of("geras"), Degree::Positive);
+    /// assert_eq!(Degree::of("geresnė"), Degree::Comparative);
+    /// assert_eq!(Degree::of("geriausiam"), Degree::Superlative);
+    /// assert_eq!(Degree::of("griausmingas"), Degree::Positive);
+    /// ```
+    pub fn of(word: &str) -> Degree {
+        let word = word.to_lowercase();
+        let has_suffix = |suffix: &str| {
+            word.rfind(suffix)
+                .is_some_and(|at| at > 0 && DEGREE_ENDINGS.contains(&&word[at + suffix.len()..]))
+        };
+        if has_suffix("iaus") {
+            Degree::Superlative
+        } else if has_suffix("esn") {
+            Degree::Comparative
+        } else {
+            Degree::Positive
+        }
+    }
+
+    /// The lowercase english name of the degree, such as `comparative`.
+    pub fn english_name(self) -> &'static str {
+        match self {
+            Degree::Positive => "positive",
+            Degree::Comparative => "comparative",
+            Degree::Superlative => "superlative",
+        }
+    }
+}
+
+/// Takes an adjective form and returns it with lithuanian accent marks for the given gender,
+/// number and case.
+///
+/// The engine decodes gender, number and case for adjectives, but not the degree of comparison:
+/// `geras`, `geresnis` and `geriausias` are separate words to it. So the degree only makes sure
+/// the word really is in that degree (see [`Degree::of`]), and is a
+/// [`PhonologyError::DegreeMismatch`] if it isn't.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::{accentuate_adjective, Case, Degree, Gender, Number};
+///
+/// let word = accentuate_adjective("geresnis", Gender::Masculine, Number::Singular, Case::Nominative, Degree::Comparative);
+/// assert_eq!(word.unwrap(), "gerèsnis");
+/// ```
+pub fn accentuate_adjective(
+    word: &str,
+    gender: Gender,
+    number: Number,
+    case: Case,
+    degree: Degree,
+) -> Result<String, PhonologyError> {
+    accentuate_adjective_with(&PhonologyEngine, word, gender, number, case, degree)
+}
+
+/// Like [`accentuate_adjective`], but gets the stress options from `resolver` instead of the
+/// engine.
+pub fn accentuate_adjective_with(
+    resolver: &impl StressResolver,
+    word: &str,
+    gender: Gender,
+    number: Number,
+    case: Case,
+    degree: Degree,
+) -> Result<String, PhonologyError> {
+    if Degree::of(word) != degree {
+        return Err(PhonologyError::DegreeMismatch {
+            word: word.to_string(),
+            degree: degree.english_name(),
+        });
+    }
+    let options = resolver.stress_options(word)?;
+    let option = options
+        .iter()
+        .find(|option| {
+            option.grammatical_case.as_deref() == Some(case.lithuanian_name())
+                && option.number.as_deref() == Some(number.lithuanian_name())
+                && option.gender.as_deref().is_some_and(|g| gender.matches(g))
+        })
+        .ok_or_else(|| PhonologyError::CaseNotFound {
+            word: word.to_string(),
+            case: Some(case.lithuanian_name().to_string()),
+            number: Some(number.lithuanian_name().to_string()),
+        })?;
+    try_stress_with_option(word, option)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StressOption;
+    use unicode_normalization::UnicodeNormalization;
+
+    fn resolver(word: &str) -> Result<Vec<StressOption>, PhonologyError> {
+        let option = |stress_type, stressed_letter_index| StressOption {
+            stress_type,
+            stressed_letter_index,
+            grammatical_case: Some(Case::Nominative.lithuanian_name().to_string()),
+            number: Some(Number::Singular.lithuanian_name().to_string()),
+            gender: Some(String::from("vyriškoji")),
+            ..Default::default()
+        };
+        match word {
+            "geras" => Ok(vec![option(2, 1)]),
+            "geresnis" => Ok(vec![option(0, 3)]),
+            "geriausias" => Ok(vec![option(1, 4)]),
+            "geriausia" => Ok(vec![option(1, 20)]),
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    fn masculine_nominative(word: &str, degree: Degree) -> Result<String, PhonologyError> {
+        accentuate_adjective_with(
+            &resolver,
+            word,
+            Gender::Masculine,
+            Number::Singular,
+            Case::Nominative,
+            degree,
+        )
+        .map(|accented| accented.nfc().collect())
+    }
+
+    #[test]
+    fn degrees_of_geras_are_stressed() {
+        let nfc = |word: &str| word.nfc().collect::<String>();
+        assert_eq!(
+            masculine_nominative("geras", Degree::Positive),
+            Ok(nfc("gẽras"))
+        );
+        assert_eq!(
+            masculine_nominative("geresnis", Degree::Comparative),
+            Ok(nfc("gerèsnis"))
+        );
+        assert_eq!(
+            masculine_nominative("geriausias", Degree::Superlative),
+            Ok(nfc("geriáusias"))
+        );
+    }
+
+    #[test]
+    fn unplaceable_stress_is_an_error() {
+        assert_eq!(
+            masculine_nominative("geriausia", Degree::Superlative),
+            Err(PhonologyError::StressIndexOutOfBounds {
+                word: String::from("geriausia"),
+                index: 20,
+                word_len: 9
+            })
+        );
+        assert!(matches!(
+            accentuate_adjective_with(
+                &resolver,
+                "geras",
+                Gender::Feminine,
+                Number::Singular,
+                Case::Nominative,
+                Degree::Positive
+            ),
+            Err(PhonologyError::CaseNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn degrees_of_geras() {
+        for word in ["geras", "gera", "gerą", "gero"] {
+            assert_eq!(Degree::of(word), Degree::Positive);
+        }
+        for word in ["geresnis", "geresnė", "geresnio", "geresniems"] {
+            assert_eq!(Degree::of(word), Degree::Comparative);
+        }
+        for word in ["geriausias", "geriausia", "geriausio", "Geriausiems"] {
+            assert_eq!(Degree::of(word), Degree::Superlative);
+        }
+        for word in ["geresnysis", "geresniosios", "geriausiasis", "geriausiųjų"] {
+            assert_ne!(Degree::of(word), Degree::Positive);
+        }
+        for word in ["griausmingas", "nesnaudus", "esnis", "ausis", "šviesus"] {
+            assert_eq!(Degree::of(word), Degree::Positive);
+        }
+    }
+
+    #[test]
+    fn mismatched_degree_is_an_error() {
+        assert_eq!(
+            masculine_nominative("geras", Degree::Superlative),
+            Err(PhonologyError::DegreeMismatch {
+                word: String::from("geras"),
+                degree: "superlative"
+            })
+        );
+    }
+
+    #[test]
+    fn gender_names() {
+        assert!(Gender::Masculine.matches("vyriškoji"));
+        assert!(Gender::Feminine.matches("Moteriškoji giminė"));
+        assert!(Gender::Neuter.matches("bevardė"));
+        assert!(!Gender::Masculine.matches("moteriškoji"));
+    }
+}
@@ -0,0 +1,245 @@
+use crate::{
+    effective_index, normalize_stress_at, out_of_bounds, select_option, stress_map,
+    syllables::{self, NucleusKind},
+    Case, NormalizedStress, PhonologyEngine, PhonologyError, StressResolver,
+};
+
+/// Where a word would be stressed and how, without writing the accent marks. See [`analyze`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StressAnalysis {
+    /// The letter that would carry the stress mark.
+    pub nucleus: char,
+    /// Which letter of the word that is, counting from 0. This is where the mark would go, so it
+    /// can be past the index the engine gave (see [`crate::accentuate`]).
+    pub index: usize,
+    /// The engine's stress type.
+    pub stress_type: u8,
+    /// The priegaidė, or `None` if the stress type isn't one the crate knows.
+    pub stress: Option<NormalizedStress>,
+    /// Whether the stress maps have a way to write the letter with the stress type. Words where
+    /// this is `false` can't be accentuated.
+    pub covered: bool,
+}
+
+/// Works out where a word would be stressed in a case, and how, without building the accented
+/// word. Words are stressed for `case` when they have a form in it, and with the engine's first
+/// option otherwise, like in [`crate::to_ssml`].
+///
+/// This is meant for checking words before accentuating lots of them: anything with `covered`
+/// set to `false` would fail.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::{analyze, Case, NormalizedStress};
+///
+/// let analysis = analyze("žodį", Case::Accusative).unwrap();
+/// assert_eq!(analysis.nucleus, 'o');
+/// assert_eq!(analysis.stress, Some(NormalizedStress::Circumflex));
+/// ```
+pub fn analyze(word: &str, case: Case) -> Result<StressAnalysis, PhonologyError> {
+    analyze_with(&PhonologyEngine, word, case)
+}
+
+/// Like [`analyze`], but gets the stress options from `resolver` instead of the engine.
+///
+/// # Examples
+///
+/// ```
+/// use lithuanian_phonology::{analyze_with, Case, PhonologyError, StressOption};
+///
+/// let resolver = |_: &str| -> Result<Vec<StressOption>, PhonologyError> {
+///     Ok(vec![StressOption {
+///         stress_type: 2,
+///         stressed_letter_index: 1,
+///         grammatical_case: Some(String::from("Galininkas")),
+///         ..Default::default()
+///     }])
+/// };
+/// let analysis = analyze_with(&resolver, "žodį", Case::Accusative).unwrap();
+/// assert_eq!((analysis.nucleus, analysis.index, analysis.covered), ('o', 1, true));
+/// ```
+pub fn analyze_with(
+    resolver: &impl StressResolver,
+    word: &str,
+    case: Case,
+) -> Result<StressAnalysis, PhonologyError> {
+    let options = resolver.stress_options(word)?;
+    let option = match select_option(
+        word,
+        &options,
+        Some(case.lithuanian_name()),
+        None,
+        &Default::default(),
+    ) {
+        Ok((option, _)) => option,
+        Err(error) => options.first().ok_or(error)?,
+    };
+    let index = effective_index(word, option);
+    let nucleus = word
+        .chars()
+        .nth(index)
+        .ok_or_else(|| out_of_bounds(word, index))?;
+    let lower = nucleus.to_lowercase().next().unwrap_or(nucleus);
+    Ok(StressAnalysis {
+        nucleus,
+        index,
+        stress_type: option.stress_type,
+        stress: normalize_stress_at(word, index, option.stress_type),
+        covered: stress_map(option.stress_type).is_some_and(|map| map.contains_key(&lower)),
+    })
+}
+
+/// Which part of its syllable the stress mark is on. See [`stress_in_syllable`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SyllablePosition {
+    /// A vowel or a diphthong, like the `õ` of `žõdį` or the `ũ` of `laũkas`.
+    Nucleus,
+    /// The `l`, `m`, `n` or `r` of a mixed diphthong, like the `l̃` of `vil̃kas`, which carries the
+    /// circumflex in place of the vowel before it.
+    Coda,
+}
+
+/// Works out which syllable of a word is stressed in a case (counting from 0) and whether the mark
+/// is on its vowel or on a sonorant after it. The option is picked like in [`analyze`].
+///
+/// A mark on a letter that isn't part of any syllable nucleus, which the engine shouldn't give, is
+/// a [`PhonologyError::UnsupportedStress`] error.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::{stress_in_syllable, Case, SyllablePosition};
+///
+/// assert_eq!(
+///     stress_in_syllable("vilkas", Case::Nominative).unwrap(),
+///     (0, SyllablePosition::Coda)
+/// );
+/// ```
+pub fn stress_in_syllable(
+    word: &str,
+    case: Case,
+) -> Result<(usize, SyllablePosition), PhonologyError> {
+    stress_in_syllable_with(&PhonologyEngine, word, case)
+}
+
+/// Like [`stress_in_syllable`], but gets the stress options from `resolver` instead of the engine.
+pub fn stress_in_syllable_with(
+    resolver: &impl StressResolver,
+    word: &str,
+    case: Case,
+) -> Result<(usize, SyllablePosition), PhonologyError> {
+    let analysis = analyze_with(resolver, word, case)?;
+    let nuclei = syllables::nuclei(word);
+    let syllable = nuclei
+        .iter()
+        .position(|n| n.contains(analysis.index))
+        .ok_or_else(|| PhonologyError::UnsupportedStress {
+            word: word.to_string(),
+            letter: analysis.nucleus,
+            stress_type: analysis.stress_type,
+        })?;
+    let nucleus = nuclei[syllable];
+    let position = if nucleus.kind == NucleusKind::MixedDiphthong && analysis.index > nucleus.start
+    {
+        SyllablePosition::Coda
+    } else {
+        SyllablePosition::Nucleus
+    };
+    Ok((syllable, position))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StressOption;
+
+    fn resolver(word: &str) -> Result<Vec<StressOption>, PhonologyError> {
+        let option = |case: &str, stress_type, stressed_letter_index| StressOption {
+            stress_type,
+            stressed_letter_index,
+            grammatical_case: Some(String::from(case)),
+            ..Default::default()
+        };
+        match word {
+            "kelio" => Ok(vec![option("Kilmininkas", 1, 3)]),
+            "Žodis" => Ok(vec![
+                option("Vardininkas", 2, 1),
+                option("Įnagininkas", 0, 3),
+            ]),
+            "brrr" => Ok(vec![option("Vardininkas", 1, 1)]),
+            "vilkas" => Ok(vec![option("Vardininkas", 2, 2)]),
+            "pilnas" => Ok(vec![option("Vardininkas", 1, 1)]),
+            "laukas" => Ok(vec![option("Vardininkas", 2, 2)]),
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    #[test]
+    fn analyzes_requested_case() {
+        let analysis = analyze_with(&resolver, "Žodis", Case::Instrumental).unwrap();
+        assert_eq!(
+            analysis,
+            StressAnalysis {
+                nucleus: 'i',
+                index: 3,
+                stress_type: 0,
+                stress: Some(NormalizedStress::Short),
+                covered: true,
+            }
+        );
+        // no locative, so the first option is used
+        let analysis = analyze_with(&resolver, "Žodis", Case::Locative).unwrap();
+        assert_eq!((analysis.nucleus, analysis.index), ('o', 1));
+    }
+
+    #[test]
+    fn moves_past_softening_i() {
+        let analysis = analyze_with(&resolver, "kelio", Case::Genitive).unwrap();
+        assert_eq!((analysis.nucleus, analysis.index), ('o', 4));
+    }
+
+    #[test]
+    fn reports_uncovered_letters() {
+        let analysis = analyze_with(&resolver, "brrr", Case::Nominative).unwrap();
+        assert_eq!(analysis.nucleus, 'r');
+        assert!(!analysis.covered);
+    }
+
+    #[test]
+    fn stress_position_in_syllable() {
+        let position = |word| stress_in_syllable_with(&resolver, word, Case::Nominative);
+        assert_eq!(position("vilkas"), Ok((0, SyllablePosition::Coda)));
+        assert_eq!(position("pilnas"), Ok((0, SyllablePosition::Nucleus)));
+        // the second letter of a diphthong is still the nucleus
+        assert_eq!(position("laukas"), Ok((0, SyllablePosition::Nucleus)));
+        assert_eq!(
+            analyze_with(&resolver, "laukas", Case::Nominative)
+                .unwrap()
+                .stress,
+            Some(NormalizedStress::Circumflex)
+        );
+        assert_eq!(
+            stress_in_syllable_with(&resolver, "Žodis", Case::Instrumental),
+            Ok((1, SyllablePosition::Nucleus))
+        );
+        assert!(matches!(
+            position("brrr"),
+            Err(PhonologyError::UnsupportedStress { letter: 'r', .. })
+        ));
+    }
+
+    #[test]
+    fn no_options_is_an_error() {
+        assert_eq!(
+            analyze_with(&resolver, "ir", Case::Nominative),
+            Err(PhonologyError::CaseNotFound {
+                word: String::from("ir"),
+                case: Some(String::from("Vardininkas")),
+                number: None,
+            })
+        );
+    }
+}
@@ -0,0 +1,217 @@
+use std::ops::Range;
+
+use crate::{
+    select_option, stressed_word, text, Case, PhonologyEngine, PhonologyError, StressResolver,
+    StressedWord,
+};
+
+/// One word of a sentence with where it is in the sentence and how it's stressed. See
+/// [`annotate_sentence`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WordAnnotation {
+    /// The word as it is in the text.
+    pub token: String,
+    /// Byte range of the word in the text, for slicing it.
+    pub range: Range<usize>,
+    /// Range of the word in the text in chars, for positioning an overlay.
+    pub char_range: Range<usize>,
+    /// The word with its accent marks and the option they came from, or `None` if the engine has
+    /// no stress for it. [`StressedWord::fell_back`] is also set when the word has no form in the
+    /// requested case and the engine's first option was used instead.
+    pub stressed: Option<StressedWord>,
+}
+
+/// Finds and stresses every word of some text, keeping track of where each one is, for drawing
+/// stress marks over the original text without tokenizing it again.
+///
+/// Words are runs of letters and the combining marks on them. Punctuation and whitespace aren't
+/// annotated, but the ranges are always ranges of the whole text. Words are stressed for `case`
+/// when they have a form in it, and with the engine's first option otherwise, like in
+/// [`crate::to_ssml`], since a sentence has words in many cases. The only error is
+/// [`PhonologyError::Engine`]; any other problem with a word leaves its
+/// [`WordAnnotation::stressed`] empty.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::{annotate_sentence, Case};
+///
+/// for word in annotate_sentence("Parašiau žodį.", Case::Accusative).unwrap() {
+///     if let Some(stressed) = &word.stressed {
+///         println!("{:?}: {}", word.char_range, stressed.accented);
+///     }
+/// }
+/// ```
+pub fn annotate_sentence(text: &str, case: Case) -> Result<Vec<WordAnnotation>, PhonologyError> {
+    annotate_sentence_with(&PhonologyEngine, text, case)
+}
+
+/// Like [`annotate_sentence`], but gets the stress options from `resolver` instead of the engine.
+pub fn annotate_sentence_with(
+    resolver: &impl StressResolver,
+    text: &str,
+    case: Case,
+) -> Result<Vec<WordAnnotation>, PhonologyError> {
+    let mut annotations = Vec::new();
+    let mut chars_before = 0;
+    let mut bytes_counted = 0;
+    for token in text::tokenize(text) {
+        chars_before += text[bytes_counted..token.range.start].chars().count();
+        bytes_counted = token.range.start;
+        if !token.is_word {
+            continue;
+        }
+        let word = token.text;
+        let stressed = stress_in_text(resolver, word, case)?;
+        let len = word.chars().count();
+        annotations.push(WordAnnotation {
+            token: word.to_string(),
+            range: token.range.clone(),
+            char_range: chars_before..chars_before + len,
+            stressed,
+        });
+    }
+    Ok(annotations)
+}
+
+/// Finds the word at a byte offset in some text and stresses it, for replacing just that word, as
+/// an editor would while someone types. Returns the byte range of the word in `text` and the word
+/// with its accent marks, or `None` if `cursor_byte` isn't in a word.
+///
+/// The cursor is in a word when it's anywhere from just before its first letter to just after its
+/// last, so a cursor right after a word that is being typed finds it. Words are found and stressed
+/// the same way as in [`annotate_sentence`]; one the engine has no stress for comes back as it is.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::{accentuate_at, Case};
+///
+/// let mut text = String::from("Parašiau žodį");
+/// if let Some((range, accented)) = accentuate_at(&text, text.len(), Case::Accusative).unwrap() {
+///     text.replace_range(range, &accented);
+/// }
+/// assert_eq!(text, "Parašiau žõdį");
+/// ```
+pub fn accentuate_at(
+    text: &str,
+    cursor_byte: usize,
+    case: Case,
+) -> Result<Option<(Range<usize>, String)>, PhonologyError> {
+    accentuate_at_with(&PhonologyEngine, text, cursor_byte, case)
+}
+
+/// Like [`accentuate_at`], but gets the stress options from `resolver` instead of the engine.
+pub fn accentuate_at_with(
+    resolver: &impl StressResolver,
+    text: &str,
+    cursor_byte: usize,
+    case: Case,
+) -> Result<Option<(Range<usize>, String)>, PhonologyError> {
+    let Some(token) = text::tokenize(text).into_iter().find(|token| {
+        token.is_word && (token.range.start..=token.range.end).contains(&cursor_byte)
+    }) else {
+        return Ok(None);
+    };
+    let accented = stress_in_text(resolver, token.text, case)?
+        .map_or_else(|| token.text.to_string(), |stressed| stressed.accented);
+    Ok(Some((token.range, accented)))
+}
+
+// Stresses a word from running text for `case`, or with the first option if it has no form in it.
+fn stress_in_text(
+    resolver: &impl StressResolver,
+    word: &str,
+    case: Case,
+) -> Result<Option<StressedWord>, PhonologyError> {
+    let options = resolver.stress_options(word)?;
+    let selected = match select_option(
+        word,
+        &options,
+        Some(case.lithuanian_name()),
+        None,
+        &Default::default(),
+    ) {
+        Ok((option, fell_back)) => Some((option, fell_back)),
+        Err(_) => options.first().map(|option| (option, true)),
+    };
+    Ok(selected.and_then(|(option, fell_back)| {
+        stressed_word(word, option.clone(), fell_back, &Default::default()).ok()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StressOption;
+
+    fn resolver(word: &str) -> Result<Vec<StressOption>, PhonologyError> {
+        let option = |case: &str, stress_type, stressed_letter_index| StressOption {
+            stress_type,
+            stressed_letter_index,
+            grammatical_case: Some(String::from(case)),
+            ..Default::default()
+        };
+        Ok(match word {
+            "Žodį" => vec![option("Galininkas", 2, 1)],
+            "gera" => vec![option("Vardininkas", 0, 3)],
+            _ => Vec::new(),
+        })
+    }
+
+    #[test]
+    fn words_keep_their_place_in_the_text() {
+        let text = "„Žodį“ – gera, ar ne?";
+        let annotations = annotate_sentence_with(&resolver, text, Case::Accusative).unwrap();
+        let tokens: Vec<&str> = annotations.iter().map(|a| a.token.as_str()).collect();
+        assert_eq!(tokens, ["Žodį", "gera", "ar", "ne"]);
+
+        let zodi = &annotations[0];
+        assert_eq!(&text[zodi.range.clone()], "Žodį");
+        assert_eq!(zodi.char_range, 1..5);
+        let stressed = zodi.stressed.as_ref().unwrap();
+        assert_eq!(stressed.accented, "Žõdį");
+        assert!(!stressed.fell_back);
+
+        let gera = &annotations[1];
+        assert_eq!(gera.char_range, 9..13);
+        assert_eq!(&text[gera.range.clone()], "gera");
+        let stressed = gera.stressed.as_ref().unwrap();
+        assert_eq!(stressed.accented, "gerà");
+        assert!(stressed.fell_back);
+
+        assert_eq!(annotations[3].char_range, 18..20);
+        assert_eq!(annotations[3].stressed, None);
+    }
+
+    #[test]
+    fn engine_errors_stop_annotating() {
+        let resolver = |word: &str| -> Result<Vec<StressOption>, PhonologyError> {
+            Err(PhonologyError::Engine {
+                word: word.to_string(),
+                message: String::from("No module named 'phonology_engine'"),
+            })
+        };
+        assert!(annotate_sentence_with(&resolver, "Labas", Case::Nominative).is_err());
+        assert_eq!(
+            annotate_sentence_with(&resolver, " ... ", Case::Nominative),
+            Ok(Vec::new())
+        );
+    }
+
+    #[test]
+    fn word_under_the_cursor() {
+        let text = "Žodį, gera";
+        let at = |cursor| accentuate_at_with(&resolver, text, cursor, Case::Accusative).unwrap();
+        assert_eq!(at(0), Some((0..6, String::from("Žõdį"))));
+        assert_eq!(at(3), Some((0..6, String::from("Žõdį"))));
+        assert_eq!(at(6), Some((0..6, String::from("Žõdį"))));
+        assert_eq!(at(7), None);
+        assert_eq!(at(text.len()), Some((8..12, String::from("gerà"))));
+        assert_eq!(at(text.len() + 1), None);
+
+        let unknown = accentuate_at_with(&resolver, "ar", 1, Case::Accusative).unwrap();
+        assert_eq!(unknown, Some((0..2, String::from("ar"))));
+    }
+}
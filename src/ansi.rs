@@ -0,0 +1,140 @@
+use std::io::{self, IsTerminal};
+
+use crate::{accentuate, graphemes, stressed_grapheme, Case, PhonologyError, StressOption};
+
+/// How [`to_ansi`] shows the stress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnsiOptions {
+    /// The SGR code the stressed letter is drawn with, such as 31 for red (the default) or 94 for
+    /// bright blue.
+    pub color: u8,
+    /// Whether to color the stressed letter at all. Turn this off when the output isn't going to a
+    /// terminal, where the escape codes would just be noise.
+    pub colored: bool,
+    /// Whether to write the accent marks too.
+    pub marks: bool,
+}
+
+impl Default for AnsiOptions {
+    fn default() -> Self {
+        Self {
+            color: 31,
+            colored: true,
+            marks: true,
+        }
+    }
+}
+
+impl AnsiOptions {
+    /// The default options, with color only if stdout is a terminal.
+    pub fn for_stdout() -> Self {
+        Self {
+            colored: io::stdout().is_terminal(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Takes a word and returns it for a terminal, with the stressed letter in color, for the case
+/// given.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::{to_ansi, AnsiOptions, Case};
+///
+/// let options = AnsiOptions { marks: false, ..Default::default() };
+/// let word = to_ansi("žodį", Case::Accusative, &options).unwrap();
+/// assert_eq!(word, "ž\x1b[31mo\x1b[0mdį");
+/// ```
+pub fn to_ansi(word: &str, case: Case, options: &AnsiOptions) -> Result<String, PhonologyError> {
+    let stressed = accentuate(
+        word,
+        Some(case.lithuanian_name()),
+        None,
+        &Default::default(),
+    )?;
+    Ok(ansi_with_option(
+        word,
+        &stressed.accented,
+        &stressed.option,
+        options,
+    ))
+}
+
+// `accented` is `word` already written with `option`'s stress mark.
+fn ansi_with_option(
+    word: &str,
+    accented: &str,
+    option: &StressOption,
+    options: &AnsiOptions,
+) -> String {
+    let text = if options.marks {
+        accented.to_string()
+    } else {
+        word.to_string()
+    };
+    if !options.colored {
+        return text;
+    }
+    let stressed = stressed_grapheme(word, option);
+    let mut colored = String::new();
+    for (i, grapheme) in graphemes(&text).enumerate() {
+        if i == stressed {
+            colored.push_str(&format!("\x1b[{}m{grapheme}\x1b[0m", options.color));
+        } else {
+            colored.push_str(grapheme);
+        }
+    }
+    colored
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::try_stress_with_option;
+
+    fn option(stress_type: u8, stressed_letter_index: usize) -> StressOption {
+        StressOption {
+            stress_type,
+            stressed_letter_index,
+            ..Default::default()
+        }
+    }
+
+    fn ansi(word: &str, option: &StressOption, options: &AnsiOptions) -> String {
+        let accented = try_stress_with_option(word, option).unwrap();
+        ansi_with_option(word, &accented, option, options)
+    }
+
+    #[test]
+    fn colors_stressed_grapheme() {
+        let options = AnsiOptions::default();
+        assert_eq!(
+            ansi("ąžuolas", &option(2, 0), &options),
+            "\x1b[31mą̃\x1b[0mžuolas"
+        );
+        assert_eq!(
+            ansi("vilkas", &option(2, 2), &options),
+            "vi\x1b[31ml̃\x1b[0mkas"
+        );
+    }
+
+    #[test]
+    fn marks_and_color_can_be_turned_off() {
+        let no_marks = AnsiOptions {
+            color: 94,
+            marks: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            ansi("gera", &option(2, 1), &no_marks),
+            "g\x1b[94me\x1b[0mra"
+        );
+        let no_color = AnsiOptions {
+            colored: false,
+            ..Default::default()
+        };
+        assert_eq!(ansi("gera", &option(2, 1), &no_color), "gẽra");
+    }
+}
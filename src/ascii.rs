@@ -0,0 +1,103 @@
+/// A way of typing the Lithuanian letters that aren't in ASCII. See [`from_ascii_lithuanian`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AsciiScheme {
+    /// A mark typed after the letter: `^` for a caron (`s^` → `š`, also `c^` and `z^`), `,` for
+    /// an ogonek (`a,` → `ą`, also `e,`, `i,` and `u,`), `.` for the dot of `ė` (`e.`) and `-` for
+    /// the macron of `ū` (`u-`).
+    Postfix,
+    /// `sh`, `ch` and `zh` for `š`, `č` and `ž`, as in English. There's no convention like this for
+    /// the vowels, so they're left as they are.
+    Digraph,
+}
+
+/// Rewrites Lithuanian typed in plain ASCII using the real Lithuanian letters, so that it can be
+/// accentuated.
+///
+/// Text typed without any marks at all, such as `zodis`, can't be restored: a `z` could just as
+/// well be a `ž`, and an `e` could be an `ę` or `ė`, so only what `scheme` marks explicitly is
+/// changed. [`AsciiScheme::Digraph`] is also ambiguous for the few words that really have a
+/// `ch` (like `choras`), which come out with a `č`, and so is [`AsciiScheme::Postfix`] for commas
+/// right after a word ending in `a`, `e`, `i` or `u`, which are read as an ogonek. Everything
+/// else, including any characters that already aren't ASCII, is kept as it is, and capitals stay
+/// capitals.
+///
+/// # Examples
+///
+/// ```
+/// use lithuanian_phonology::{from_ascii_lithuanian, AsciiScheme};
+///
+/// assert_eq!(from_ascii_lithuanian("z^odi,", AsciiScheme::Postfix), "žodį");
+/// assert_eq!(from_ascii_lithuanian("Su-nu, ", AsciiScheme::Postfix), "Sūnų ");
+/// assert_eq!(from_ascii_lithuanian("zhodis", AsciiScheme::Digraph), "žodis");
+/// assert_eq!(from_ascii_lithuanian("SHIMTAS", AsciiScheme::Digraph), "ŠIMTAS");
+/// ```
+pub fn from_ascii_lithuanian(input: &str, scheme: AsciiScheme) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        let lower = c.to_ascii_lowercase();
+        let marked = chars
+            .peek()
+            .and_then(|&next| combine(scheme, lower, next.to_ascii_lowercase()));
+        match marked {
+            Some(letter) => {
+                chars.next();
+                if c.is_uppercase() {
+                    output.extend(letter.to_uppercase());
+                } else {
+                    output.push(letter);
+                }
+            }
+            None => output.push(c),
+        }
+    }
+    output
+}
+
+fn combine(scheme: AsciiScheme, letter: char, next: char) -> Option<char> {
+    let combined = match (scheme, letter, next) {
+        (AsciiScheme::Postfix, 'c', '^') | (AsciiScheme::Digraph, 'c', 'h') => 'č',
+        (AsciiScheme::Postfix, 's', '^') | (AsciiScheme::Digraph, 's', 'h') => 'š',
+        (AsciiScheme::Postfix, 'z', '^') | (AsciiScheme::Digraph, 'z', 'h') => 'ž',
+        (AsciiScheme::Postfix, 'a', ',') => 'ą',
+        (AsciiScheme::Postfix, 'e', ',') => 'ę',
+        (AsciiScheme::Postfix, 'i', ',') => 'į',
+        (AsciiScheme::Postfix, 'u', ',') => 'ų',
+        (AsciiScheme::Postfix, 'e', '.') => 'ė',
+        (AsciiScheme::Postfix, 'u', '-') => 'ū',
+        _ => return None,
+    };
+    Some(combined)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn postfix_marks() {
+        assert_eq!(
+            from_ascii_lithuanian("c^ia, e,z^e.s u-kis", AsciiScheme::Postfix),
+            "čią ęžės ūkis"
+        );
+        // a comma that isn't after a vowel it can go on is just punctuation
+        assert_eq!(
+            from_ascii_lithuanian("labas, vakaras", AsciiScheme::Postfix),
+            "labas, vakaras"
+        );
+        assert_eq!(from_ascii_lithuanian("E.", AsciiScheme::Postfix), "Ė");
+    }
+
+    #[test]
+    fn digraphs() {
+        assert_eq!(
+            from_ascii_lithuanian("Chesnakas ir zhuvis", AsciiScheme::Digraph),
+            "Česnakas ir žuvis"
+        );
+        assert_eq!(from_ascii_lithuanian("a,", AsciiScheme::Digraph), "a,");
+        assert_eq!(
+            from_ascii_lithuanian("jau šalta", AsciiScheme::Digraph),
+            "jau šalta"
+        );
+    }
+}
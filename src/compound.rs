@@ -0,0 +1,207 @@
+use pyo3::Python;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::{
+    process_raw_py, select_option, try_stress_with_option, PhonologyError, RawUnit, StressOption,
+};
+
+/// One of the parts the engine split a word into, with its own stress options.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StressUnit {
+    /// Index (in chars) of the first letter of the part in the word.
+    pub offset: usize,
+    /// The part as the engine read it, like `baltas` in `juodai-baltas`.
+    pub text: String,
+    /// The stress options of the part. Their indices count from the start of the part, not of
+    /// the word.
+    pub stress_options: Vec<StressOption>,
+}
+
+/// Returns every part the engine splits a word into, with its stress options. Most words are one
+/// part, but some long or hyphenated compounds come back from the engine as several, each stressed
+/// on its own, and [`crate::get_stress_options`] only reads the first of them.
+///
+/// Parts without stress options, like the hyphen of `juodai-baltas`, are left out. The engine is
+/// given the word NFC normalized, and the offsets count its letters in that form.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::stress_units;
+///
+/// for unit in stress_units("juodai-baltas").unwrap() {
+///     println!("{} at {}: {} options", unit.text, unit.offset, unit.stress_options.len());
+/// }
+/// ```
+pub fn stress_units(word: &str) -> Result<Vec<StressUnit>, PhonologyError> {
+    let engine_form: String = word.nfc().collect();
+    Python::with_gil(|py| {
+        let raw = process_raw_py(py, &engine_form)?;
+        units_in(&engine_form, &raw)
+    })
+}
+
+/// Takes a word and returns it with lithuanian accent marks on every part the engine splits it
+/// into (see [`stress_units`]), each part stressed with its option for the case and number as in
+/// [`crate::get_accentuation`] and put back at its place in the word. For a word the engine keeps
+/// whole, this is the same as [`crate::get_accentuation`].
+///
+/// A part that has no option for the case is a [`PhonologyError::CaseNotFound`] error naming the
+/// part. The result is in NFC.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::accentuate_compound;
+///
+/// let accented = accentuate_compound("juodai-baltas", Some("Vardininkas"), None).unwrap();
+/// assert_eq!(accented, "juodaĩ-báltas");
+/// ```
+pub fn accentuate_compound(
+    word: &str,
+    case: Option<&str>,
+    number: Option<&str>,
+) -> Result<String, PhonologyError> {
+    let engine_form: String = word.nfc().collect();
+    render_units(&engine_form, &stress_units(word)?, case, number)
+}
+
+// Finds each word of the engine's output in `word`, in order. Words without a `word` field can
+// only be placed when they are the only one, at the start.
+fn units_in(word: &str, raw: &[RawUnit]) -> Result<Vec<StressUnit>, PhonologyError> {
+    let parts: Vec<_> = raw
+        .iter()
+        .flat_map(|unit| unit.groups.iter().flatten())
+        .collect();
+    let mut units = Vec::new();
+    let mut searched = 0;
+    for part in &parts {
+        if part.stress_options.is_empty() {
+            continue;
+        }
+        let text = match (&part.word, parts.len()) {
+            (Some(text), _) => text.clone(),
+            (None, 1) => word.to_string(),
+            (None, _) => continue,
+        };
+        let found = word[searched..]
+            .find(&text)
+            .ok_or_else(|| PhonologyError::Engine {
+                word: word.to_string(),
+                message: format!("the engine split out a part '{text}' that isn't in the word"),
+            })?;
+        let start = searched + found;
+        units.push(StressUnit {
+            offset: word[..start].chars().count(),
+            text,
+            stress_options: part.stress_options.clone(),
+        });
+        searched = start + units.last().map_or(0, |unit| unit.text.len());
+    }
+    Ok(units)
+}
+
+// Stresses each unit with its option for the case and number, keeping everything in between as it
+// is.
+fn render_units(
+    word: &str,
+    units: &[StressUnit],
+    case: Option<&str>,
+    number: Option<&str>,
+) -> Result<String, PhonologyError> {
+    let chars: Vec<char> = word.chars().collect();
+    let mut accented = String::with_capacity(word.len() + 2 * units.len());
+    let mut rest = 0;
+    for unit in units {
+        let (option, _) = select_option(
+            &unit.text,
+            &unit.stress_options,
+            case,
+            number,
+            &Default::default(),
+        )?;
+        accented.extend(&chars[rest..unit.offset]);
+        accented.push_str(&try_stress_with_option(&unit.text, option)?);
+        rest = unit.offset + unit.text.chars().count();
+    }
+    accented.extend(chars.get(rest..).unwrap_or_default());
+    Ok(accented.nfc().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RawWord;
+
+    fn part(text: &str, stress_type: u8, stressed_letter_index: usize) -> RawWord {
+        RawWord {
+            word: Some(text.to_string()),
+            stress_options: vec![StressOption {
+                stress_type,
+                stressed_letter_index,
+                grammatical_case: (text == "baltas").then(|| String::from("Vardininkas")),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    // what the engine gives for a hyphenated compound: the two halves and the hyphen between them
+    fn juodai_baltas() -> Vec<RawUnit> {
+        let hyphen = RawWord {
+            word: Some(String::from("-")),
+            ..Default::default()
+        };
+        vec![RawUnit {
+            groups: vec![vec![part("juodai", 2, 5), hyphen, part("baltas", 1, 1)]],
+        }]
+    }
+
+    #[test]
+    fn every_unit_is_stressed() {
+        let word = "juodai-baltas";
+        let units = units_in(word, &juodai_baltas()).unwrap();
+        let offsets: Vec<_> = units.iter().map(|u| (u.offset, u.text.as_str())).collect();
+        assert_eq!(offsets, [(0, "juodai"), (7, "baltas")]);
+        assert_eq!(
+            render_units(word, &units, Some("Vardininkas"), None).unwrap(),
+            "juodaĩ-báltas"
+        );
+        // a double surname, with a capital in the middle
+        let raw = vec![RawUnit {
+            groups: vec![vec![part("Kazlauskienė", 1, 8), part("Jonaitienė", 1, 6)]],
+        }];
+        let word = "Kazlauskienė-Jonaitienė";
+        let units = units_in(word, &raw).unwrap();
+        assert_eq!(
+            render_units(word, &units, None, None).unwrap(),
+            "Kazlauskíenė-Jonaitíenė"
+        );
+        let word = "juodai-baltas";
+        let units = units_in(word, &juodai_baltas()).unwrap();
+        assert!(matches!(
+            render_units(word, &units, Some("Kilmininkas"), None),
+            Err(PhonologyError::CaseNotFound { word, .. }) if word == "baltas"
+        ));
+    }
+
+    #[test]
+    fn single_units() {
+        let mut unnamed = part("žodį", 2, 1);
+        unnamed.word = None;
+        let raw = vec![RawUnit {
+            groups: vec![vec![unnamed]],
+        }];
+        let units = units_in("žodį", &raw).unwrap();
+        assert_eq!(render_units("žodį", &units, None, None).unwrap(), "žõdį");
+
+        let elsewhere = vec![RawUnit {
+            groups: vec![vec![part("namas", 0, 1)]],
+        }];
+        assert!(matches!(
+            units_in("žodį", &elsewhere),
+            Err(PhonologyError::Engine { .. })
+        ));
+    }
+}
@@ -0,0 +1,109 @@
+use crate::{
+    effective_index, normalize_stress_at, select_option, syllables, try_stress_with_option, Case,
+    PhonologyEngine, PhonologyError, StressResolver,
+};
+
+/// Describes a word's stress in a case as a fragment of the MISC column of a CoNLL-U file, for
+/// adding stress to the tokens of a treebank or a parser's output. The fields are:
+///
+/// - `Stress`: which syllable is stressed, counting from 1 like CoNLL-U counts tokens
+/// - `StressType`: `Short`, `Acute` or `Circumflex` (see [`crate::normalize_stress_type`])
+/// - `Accented`: the word with its accent marks
+///
+/// A word that has no stress in the case, because the engine has no form of it in the case or
+/// its stress can't be written, is just `Stress=None`, so every token can get a MISC fragment. The
+/// fragment can be joined to other MISC fields with another `|`. Only errors calling the engine
+/// are errors.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::{to_conllu_misc, Case};
+///
+/// assert_eq!(
+///     to_conllu_misc("žodį", Case::Accusative).unwrap(),
+///     "Stress=1|StressType=Circumflex|Accented=žõdį"
+/// );
+/// ```
+pub fn to_conllu_misc(word: &str, case: Case) -> Result<String, PhonologyError> {
+    to_conllu_misc_with(&PhonologyEngine, word, case)
+}
+
+/// Like [`to_conllu_misc`], but gets the stress options from `resolver` instead of the engine.
+pub fn to_conllu_misc_with(
+    resolver: &impl StressResolver,
+    word: &str,
+    case: Case,
+) -> Result<String, PhonologyError> {
+    let options = resolver.stress_options(word)?;
+    let Ok((option, _)) = select_option(
+        word,
+        &options,
+        Some(case.lithuanian_name()),
+        None,
+        &Default::default(),
+    ) else {
+        return Ok(String::from("Stress=None"));
+    };
+
+    let index = effective_index(word, option);
+    let stress = normalize_stress_at(word, index, option.stress_type);
+    let syllable = syllables::nucleus_ordinal(word, index);
+    match (syllable, stress, try_stress_with_option(word, option)) {
+        (Some(syllable), Some(stress), Ok(accented)) => Ok(format!(
+            "Stress={}|StressType={}|Accented={accented}",
+            syllable + 1,
+            capitalized(stress.english_name()),
+        )),
+        _ => Ok(String::from("Stress=None")),
+    }
+}
+
+fn capitalized(name: &str) -> String {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StressOption;
+
+    fn resolver(word: &str) -> Result<Vec<StressOption>, PhonologyError> {
+        let option = |stress_type, stressed_letter_index| StressOption {
+            stress_type,
+            stressed_letter_index,
+            grammatical_case: Some(String::from("Galininkas")),
+            ..Default::default()
+        };
+        Ok(match word {
+            "žodį" => vec![option(2, 1)],
+            "vilką" => vec![option(2, 2)],
+            "gerą" => vec![option(0, 1)],
+            "brrr" => vec![option(1, 1)],
+            _ => Vec::new(),
+        })
+    }
+
+    #[test]
+    fn misc_fields() {
+        let misc = |word| to_conllu_misc_with(&resolver, word, Case::Accusative).unwrap();
+        assert_eq!(misc("žodį"), "Stress=1|StressType=Circumflex|Accented=žõdį");
+        assert_eq!(
+            misc("vilką"),
+            "Stress=1|StressType=Circumflex|Accented=vil̃ką"
+        );
+        assert_eq!(misc("gerą"), "Stress=1|StressType=Short|Accented=gèrą");
+    }
+
+    #[test]
+    fn no_stress_is_none() {
+        let misc = |word, case| to_conllu_misc_with(&resolver, word, case).unwrap();
+        assert_eq!(misc("žodį", Case::Genitive), "Stress=None");
+        assert_eq!(misc("ir", Case::Accusative), "Stress=None");
+        assert_eq!(misc("brrr", Case::Accusative), "Stress=None");
+    }
+}
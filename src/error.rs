@@ -0,0 +1,197 @@
+use std::{error::Error, fmt};
+
+/// Everything that can go wrong while accentuating a word. Errors about a word say which word
+/// (and which case, if there was one) caused them; the ones about setting up the engine
+/// ([`ModuleNotFound`](Self::ModuleNotFound), [`EngineSetup`](Self::EngineSetup)), about a
+/// tag ([`UnsupportedTag`](Self::UnsupportedTag)) and cache errors that happen outside of a
+/// lookup ([`Cache`](Self::Cache) with no `word`) don't have one to name.
+///
+/// # Examples
+///
+/// ```
+/// use lithuanian_phonology::PhonologyError;
+///
+/// let error = PhonologyError::CaseNotFound {
+///     word: String::from("žodį"),
+///     case: Some(String::from("Galininkas")),
+///     number: None,
+/// };
+/// assert_eq!(
+///     error.to_string(),
+///     "no stress option for case 'Galininkas' in word 'žodį'"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PhonologyError {
+    /// Calling into `phonology_engine` failed, usually because it isn't installed. `message` is
+    /// the Python exception.
+    Engine { word: String, message: String },
+    /// Python couldn't find the `phonology_engine` module. `message` is the Python exception.
+    ModuleNotFound { message: String },
+    /// `phonology_engine` was found, but creating its engine failed. `message` is the Python
+    /// exception.
+    EngineSetup { message: String },
+    /// One of the stress options the engine returned had a field that couldn't be read.
+    /// `message` is the Python exception.
+    InvalidField {
+        word: String,
+        field: &'static str,
+        message: String,
+    },
+    /// None of the stress options the engine returned matched the requested case and number.
+    CaseNotFound {
+        word: String,
+        case: Option<String>,
+        number: Option<String>,
+    },
+    /// The vocative was requested, but the engine has no vocative for the word. Many nouns don't
+    /// have a distinct one.
+    NoVocative { word: String },
+    /// An adjective was requested in a degree of comparison that the word isn't in.
+    DegreeMismatch { word: String, degree: &'static str },
+    /// None of the stress options the engine returned were in the requested mood.
+    MoodNotFound { word: String, mood: &'static str },
+    /// A morphological tag wasn't in a scheme the crate understands.
+    UnsupportedTag { tag: String },
+    /// A word has fewer syllable nuclei than the one stress was asked to go on.
+    NucleusOutOfRange {
+        word: String,
+        ordinal: usize,
+        nuclei: usize,
+    },
+    /// Reading from or writing to the stress cache failed. `word` is the word being looked up, if
+    /// there was one.
+    Cache {
+        word: Option<String>,
+        message: String,
+    },
+    /// The engine said to stress a letter that isn't in the word, and counting it from the end of
+    /// the word doesn't land on a letter that can be stressed either.
+    InvalidStressIndex { word: String, index: i64 },
+    /// A stress mark was to go on a letter past the end of the word. `word_len` is how many
+    /// letters (chars) the word has.
+    StressIndexOutOfBounds {
+        word: String,
+        index: usize,
+        word_len: usize,
+    },
+    /// The engine can't do what was asked, like coming up with the other forms of a word.
+    /// `operation` says what it was, like `conjugate verbs`.
+    NotSupported {
+        word: String,
+        operation: &'static str,
+    },
+    /// An IPA transcription couldn't be matched up with the word it was given for. `reason` says
+    /// why, like `it has no primary stress mark`.
+    IpaMismatch {
+        word: String,
+        ipa: String,
+        reason: String,
+    },
+    /// There is no way to write a letter with the requested stress type.
+    UnsupportedStress {
+        word: String,
+        letter: char,
+        stress_type: u8,
+    },
+}
+
+impl fmt::Display for PhonologyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Engine { word, message } => {
+                write!(f, "phonology_engine failed on word '{word}': {message}")
+            }
+            Self::ModuleNotFound { message } => {
+                write!(f, "phonology_engine isn't installed: {message}")
+            }
+            Self::EngineSetup { message } => {
+                write!(f, "phonology_engine couldn't be started: {message}")
+            }
+            Self::InvalidField {
+                word,
+                field,
+                message,
+            } => write!(
+                f,
+                "unable to read '{field}' of a stress option for word '{word}': {message}"
+            ),
+            Self::CaseNotFound { word, case, number } => match (case, number) {
+                (Some(c), Some(n)) => write!(
+                    f,
+                    "no stress option for case '{c}' and number '{n}' in word '{word}'"
+                ),
+                (Some(c), None) => write!(f, "no stress option for case '{c}' in word '{word}'"),
+                (None, Some(n)) => {
+                    write!(f, "no stress option for number '{n}' in word '{word}'")
+                }
+                (None, None) => write!(f, "no stress options in word '{word}'"),
+            },
+            Self::NoVocative { word } => write!(f, "word '{word}' has no vocative"),
+            Self::DegreeMismatch { word, degree } => {
+                write!(f, "word '{word}' isn't in the {degree} degree")
+            }
+            Self::MoodNotFound { word, mood } => {
+                write!(f, "no stress option in the {mood} mood in word '{word}'")
+            }
+            Self::UnsupportedTag { tag } => {
+                write!(f, "'{tag}' is not a supported MULTEXT-East noun tag")
+            }
+            Self::NucleusOutOfRange {
+                word,
+                ordinal,
+                nuclei,
+            } => write!(
+                f,
+                "can't stress nucleus {ordinal} of word '{word}', which only has {nuclei}"
+            ),
+            Self::Cache {
+                word: Some(word),
+                message,
+            } => write!(f, "stress cache failed on word '{word}': {message}"),
+            Self::Cache {
+                word: None,
+                message,
+            } => write!(f, "stress cache failed: {message}"),
+            Self::InvalidStressIndex { word, index } => write!(
+                f,
+                "stressed letter index {index} is outside of word '{word}'"
+            ),
+            Self::StressIndexOutOfBounds {
+                word,
+                index,
+                word_len,
+            } => write!(
+                f,
+                "can't stress letter {index} of word '{word}', which only has {word_len}"
+            ),
+            Self::NotSupported { word, operation } => {
+                write!(f, "phonology_engine can't {operation} (word '{word}')")
+            }
+            Self::IpaMismatch { word, ipa, reason } => {
+                write!(
+                    f,
+                    "can't find the stress of '{ipa}' in word '{word}': {reason}"
+                )
+            }
+            Self::UnsupportedStress {
+                word,
+                letter,
+                stress_type,
+            } => write!(
+                f,
+                "letter '{letter}' in word '{word}' can't take stress type {stress_type}"
+            ),
+        }
+    }
+}
+
+impl Error for PhonologyError {}
+
+/// Errors are serialized as their message.
+#[cfg(feature = "serde")]
+impl serde::Serialize for PhonologyError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
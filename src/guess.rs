@@ -0,0 +1,298 @@
+use crate::{Case, StressOption};
+
+const SINGULAR: &str = "vienaskaita";
+const PLURAL: &str = "daugiskaita";
+
+/// A productive suffix whose stress doesn't depend on the word it's attached to.
+struct Paradigm {
+    /// The suffix without its ending, like `ytoj` for `-ytojas`.
+    suffix: &'static str,
+    /// Which letter of the suffix is stressed.
+    offset: usize,
+    stress_type: u8,
+    /// Roughly what share of the nouns with the suffix, weighted by how often they're used, are
+    /// stressed this way.
+    reliability: f64,
+    /// The endings the suffix can be followed by while the stress stays on it.
+    endings: &'static [(Case, &'static str, &'static str)],
+}
+
+/// Every ending of a masculine `-as` noun.
+const AS_ENDINGS: &[(Case, &str, &str)] = &[
+    (Case::Nominative, SINGULAR, "as"),
+    (Case::Genitive, SINGULAR, "o"),
+    (Case::Dative, SINGULAR, "ui"),
+    (Case::Accusative, SINGULAR, "ą"),
+    (Case::Instrumental, SINGULAR, "u"),
+    (Case::Locative, SINGULAR, "uje"),
+    (Case::Vocative, SINGULAR, "au"),
+    (Case::Nominative, PLURAL, "ai"),
+    (Case::Genitive, PLURAL, "ų"),
+    (Case::Dative, PLURAL, "ams"),
+    (Case::Accusative, PLURAL, "us"),
+    (Case::Instrumental, PLURAL, "ais"),
+    (Case::Locative, PLURAL, "uose"),
+    (Case::Vocative, PLURAL, "ai"),
+];
+
+/// The endings of a masculine `-as` noun of the second accent paradigm that don't draw the stress
+/// onto themselves.
+const AS_STEM_ENDINGS: &[(Case, &str, &str)] = &[
+    (Case::Nominative, SINGULAR, "as"),
+    (Case::Genitive, SINGULAR, "o"),
+    (Case::Dative, SINGULAR, "ui"),
+    (Case::Accusative, SINGULAR, "ą"),
+    (Case::Nominative, PLURAL, "ai"),
+    (Case::Genitive, PLURAL, "ų"),
+    (Case::Dative, PLURAL, "ams"),
+    (Case::Instrumental, PLURAL, "ais"),
+];
+
+/// The endings of a masculine `-is` noun of the second accent paradigm that don't draw the stress
+/// onto themselves.
+const IS_STEM_ENDINGS: &[(Case, &str, &str)] = &[
+    (Case::Nominative, SINGULAR, "is"),
+    (Case::Genitive, SINGULAR, "io"),
+    (Case::Dative, SINGULAR, "iui"),
+    (Case::Accusative, SINGULAR, "į"),
+    (Case::Nominative, PLURAL, "iai"),
+    (Case::Genitive, PLURAL, "ių"),
+    (Case::Dative, PLURAL, "iams"),
+    (Case::Instrumental, PLURAL, "iais"),
+];
+
+/// The endings of a feminine `-ė` noun of the second accent paradigm that don't draw the stress
+/// onto themselves.
+const E_STEM_ENDINGS: &[(Case, &str, &str)] = &[
+    (Case::Nominative, SINGULAR, "ė"),
+    (Case::Genitive, SINGULAR, "ės"),
+    (Case::Dative, SINGULAR, "ei"),
+    (Case::Accusative, SINGULAR, "ę"),
+    (Case::Nominative, PLURAL, "ės"),
+    (Case::Dative, PLURAL, "ėms"),
+    (Case::Instrumental, PLURAL, "ėmis"),
+];
+
+const PARADIGMS: &[Paradigm] = &[
+    // mokýtojas, rašýtojas
+    Paradigm {
+        suffix: "ytoj",
+        offset: 0,
+        stress_type: 1,
+        reliability: 0.98,
+        endings: AS_ENDINGS,
+    },
+    // gelbė́tojas
+    Paradigm {
+        suffix: "ėtoj",
+        offset: 0,
+        stress_type: 1,
+        reliability: 0.95,
+        endings: AS_ENDINGS,
+    },
+    // draugỹstė, kvailỹstė
+    Paradigm {
+        suffix: "yst",
+        offset: 0,
+        stress_type: 2,
+        reliability: 0.95,
+        endings: E_STEM_ENDINGS,
+    },
+    // namùkas, sūnùkas, arkliùkas
+    Paradigm {
+        suffix: "uk",
+        offset: 0,
+        stress_type: 0,
+        reliability: 0.9,
+        endings: AS_STEM_ENDINGS,
+    },
+    // namẽlis, medẽlis
+    Paradigm {
+        suffix: "el",
+        offset: 0,
+        stress_type: 2,
+        reliability: 0.8,
+        endings: IS_STEM_ENDINGS,
+    },
+    // brolė̃lis
+    Paradigm {
+        suffix: "ėl",
+        offset: 0,
+        stress_type: 2,
+        reliability: 0.85,
+        endings: IS_STEM_ENDINGS,
+    },
+];
+
+/// Guesses where a noun is stressed from its suffix and ending alone, without the engine.
+///
+/// Only a few productive suffixes are covered, ones whose stress is the same for every word that
+/// has them:
+///
+/// - agent nouns in `-ytojas` and `-ėtojas` (`mokýtojas`), which keep an acute on the suffix in
+///   every case
+/// - abstract nouns in `-ystė` (`draugỹstė`), with a circumflex on the suffix
+/// - diminutives in `-ukas`/`-iukas` (`namùkas`) and `-elis`/`-ėlis` (`namẽlis`)
+///
+/// The last three are in the second accent paradigm, where the instrumental and locative singular
+/// and the accusative plural move the stress onto the ending. Those forms, and anything that
+/// doesn't end in one of the suffixes above, return `None` rather than a guess. The suffix also
+/// has to follow at least one vowel, so that short roots which happen to end the same way aren't
+/// mistaken for derived words. For the forms that are covered, the guess should only be wrong for
+/// the odd lexicalized word that isn't really built with the suffix.
+///
+/// # Examples
+///
+/// ```
+/// use lithuanian_phonology::{guess_stress_by_ending, Case};
+///
+/// let option = guess_stress_by_ending("mokytojas", Case::Nominative).unwrap();
+/// assert_eq!((option.stressed_letter_index, option.stress_type), (3, 1));
+///
+/// let option = guess_stress_by_ending("namukai", Case::Nominative).unwrap();
+/// assert_eq!(option.number.as_deref(), Some("daugiskaita"));
+///
+/// assert_eq!(guess_stress_by_ending("namuku", Case::Instrumental), None);
+/// assert_eq!(guess_stress_by_ending("gera", Case::Nominative), None);
+/// ```
+pub fn guess_stress_by_ending(word: &str, case: Case) -> Option<StressOption> {
+    guess_by_suffix(word, case).map(|(option, _)| option)
+}
+
+/// Like [`guess_stress_by_ending`], but with a confidence between 0 and 1 in the guess, for
+/// deciding whether it's good enough to show.
+///
+/// The confidence is how reliable the suffix the guess was made from is: roughly what share of the
+/// nouns ending in it, weighted by how often each one is used, are stressed the way the guess says.
+/// Agent nouns in `-ytojas` almost never break the pattern (0.98), `-ėtojas` and `-ystė` rarely do
+/// (0.95), and the diminutive suffixes, which more often turn up at the end of words that aren't
+/// diminutives, are lower: `-ukas` is 0.9, `-ėlis` 0.85 and `-elis` 0.8. These are estimates, meant
+/// for ranking guesses and picking a threshold rather than as exact probabilities.
+///
+/// The option comes back with a `guessed` detail of `true` and a `confidence` detail with the
+/// same number, so [`StressOption::is_confident`] reports it as a guess. With the `offline`
+/// feature, words in the built-in table (see `lookup_offline`) are looked up first, and
+/// their option for `case` comes back as it is with a confidence of 1.
+///
+/// # Examples
+///
+/// ```
+/// use lithuanian_phonology::{guess_with_confidence, Case};
+///
+/// let (option, confidence) = guess_with_confidence("mokytojas", Case::Nominative).unwrap();
+/// assert_eq!(option.stressed_letter_index, 3);
+/// assert!(confidence > 0.9);
+/// assert!(!option.is_confident());
+///
+/// let (_, confidence) = guess_with_confidence("kamuolėlis", Case::Nominative).unwrap();
+/// assert!(confidence < 0.9);
+/// ```
+pub fn guess_with_confidence(word: &str, case: Case) -> Option<(StressOption, f64)> {
+    #[cfg(feature = "offline")]
+    if let Some(option) = crate::lookup_offline(word).and_then(|options| {
+        options
+            .into_iter()
+            .find(|option| option.grammatical_case.as_deref() == Some(case.lithuanian_name()))
+    }) {
+        return Some((option, 1.0));
+    }
+
+    let (mut option, paradigm) = guess_by_suffix(word, case)?;
+    let confidence = paradigm.reliability;
+    option
+        .details
+        .insert(String::from("guessed"), String::from("true"));
+    option
+        .details
+        .insert(String::from("confidence"), confidence.to_string());
+    Some((option, confidence))
+}
+
+fn guess_by_suffix(word: &str, case: Case) -> Option<(StressOption, &'static Paradigm)> {
+    let letters: Vec<char> = word
+        .chars()
+        .map(|c| c.to_lowercase().next().unwrap_or(c))
+        .collect();
+
+    for paradigm in PARADIGMS {
+        let suffix: Vec<char> = paradigm.suffix.chars().collect();
+        for &(ending_case, number, ending) in paradigm.endings {
+            if ending_case != case {
+                continue;
+            }
+            let ending: Vec<char> = ending.chars().collect();
+            let Some(stem_len) = letters.len().checked_sub(suffix.len() + ending.len()) else {
+                continue;
+            };
+            if letters[stem_len..stem_len + suffix.len()] != suffix[..]
+                || letters[stem_len + suffix.len()..] != ending[..]
+                || !letters[..stem_len]
+                    .iter()
+                    .any(|&c| crate::syllables::is_vowel(c))
+            {
+                continue;
+            }
+            let option = StressOption {
+                stress_type: paradigm.stress_type,
+                stressed_letter_index: stem_len + paradigm.offset,
+                grammatical_case: Some(case.lithuanian_name().to_string()),
+                number: Some(number.to_string()),
+                ..Default::default()
+            };
+            return Some((option, paradigm));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::create_stresed_word;
+    use unicode_normalization::UnicodeNormalization;
+
+    fn guess(word: &str, case: Case) -> Option<String> {
+        let option = guess_stress_by_ending(word, case)?;
+        let stressed = create_stresed_word(word, option.stress_type, option.stressed_letter_index);
+        Some(stressed.ok()?.nfc().collect())
+    }
+
+    #[test]
+    fn agent_nouns_are_stressed_in_every_case() {
+        assert_eq!(guess("mokytojas", Case::Nominative).unwrap(), "mokýtojas");
+        assert_eq!(guess("mokytojui", Case::Dative).unwrap(), "mokýtojui");
+        assert_eq!(
+            guess("rašytojais", Case::Instrumental).unwrap(),
+            "rašýtojais"
+        );
+        assert_eq!(guess("gelbėtojo", Case::Genitive).unwrap(), "gelbė́tojo");
+    }
+
+    #[test]
+    fn second_paradigm_suffixes() {
+        assert_eq!(guess("draugystė", Case::Nominative).unwrap(), "draugỹstė");
+        assert_eq!(guess("namukas", Case::Nominative).unwrap(), "namùkas");
+        assert_eq!(guess("namelį", Case::Accusative).unwrap(), "namẽlį");
+        assert_eq!(guess("namukus", Case::Accusative), None);
+        assert_eq!(guess("draugystėje", Case::Locative), None);
+    }
+
+    #[test]
+    fn ending_has_to_match_case() {
+        assert_eq!(guess("mokytojas", Case::Genitive), None);
+        assert_eq!(guess("kelis", Case::Nominative), None);
+    }
+
+    #[test]
+    fn confidence_follows_the_suffix() {
+        let confidence = |word| guess_with_confidence(word, Case::Nominative).map(|(_, c)| c);
+        assert!(confidence("mokytojas") > confidence("namukas"));
+        assert!(confidence("namukas") > confidence("namelis"));
+        assert_eq!(confidence("gera"), None);
+
+        let (option, confidence) = guess_with_confidence("draugystė", Case::Nominative).unwrap();
+        assert_eq!((option.stressed_letter_index, option.stress_type), (5, 2));
+        assert_eq!(option.details["confidence"].parse(), Ok(confidence));
+        assert_eq!(option.details["guessed"], "true");
+    }
+}
@@ -0,0 +1,401 @@
+use std::ops::Range;
+
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{
+    accentuate_forced,
+    syllables::{self, NucleusKind},
+    PhonologyError,
+};
+
+const GRAVE: char = '\u{0300}';
+const ACUTE: char = '\u{0301}';
+const TILDE: char = '\u{0303}';
+
+/// One visible letter of a word, without its stress mark.
+struct Letter {
+    range: Range<usize>,
+    base: char,
+    stress: Option<char>,
+}
+
+fn letters(word: &str) -> Vec<Letter> {
+    word.grapheme_indices(true)
+        .map(|(start, grapheme)| {
+            let mut stress = None;
+            let base: String = grapheme
+                .nfd()
+                .filter(|&c| {
+                    let is_stress = [GRAVE, ACUTE, TILDE].contains(&c);
+                    if is_stress {
+                        stress = Some(c);
+                    }
+                    !is_stress
+                })
+                .nfc()
+                .collect();
+            let base = base.chars().next().unwrap_or_default();
+            Letter {
+                range: start..start + grapheme.len(),
+                base: base.to_lowercase().next().unwrap_or(base),
+                stress,
+            }
+        })
+        .collect()
+}
+
+fn vowel(c: char, stress: Option<char>) -> &'static str {
+    // a and e are lengthened when they carry an acute or a circumflex
+    let lengthened = matches!(stress, Some(ACUTE | TILDE));
+    match c {
+        'a' if lengthened => "aː",
+        'a' => "ɐ",
+        'ą' => "aː",
+        'e' if lengthened => "æː",
+        'e' => "ɛ",
+        'ę' => "æː",
+        'ė' => "eː",
+        'i' => "ɪ",
+        'į' | 'y' => "iː",
+        'o' => "oː",
+        'u' => "ʊ",
+        'ų' | 'ū' => "uː",
+        _ => "",
+    }
+}
+
+fn diphthong(first: char, second: char) -> &'static str {
+    match (first, second) {
+        ('a', 'i') => "ɐɪ",
+        ('a', 'u') => "ɐʊ",
+        ('e', 'i') => "ɛɪ",
+        ('e', 'u') => "ɛʊ",
+        ('i', 'e') => "iɛ",
+        ('u', 'i') => "ʊɪ",
+        ('u', 'o') => "uɔ",
+        _ => "",
+    }
+}
+
+fn consonant(c: char) -> &'static str {
+    match c {
+        'b' => "b",
+        'c' => "t͡s",
+        'č' => "t͡ʃ",
+        'd' => "d",
+        'f' => "f",
+        'g' => "ɡ",
+        'h' => "ɣ",
+        'j' => "j",
+        'k' => "k",
+        'l' => "l",
+        'm' => "m",
+        'n' => "n",
+        'p' => "p",
+        'r' => "r",
+        's' => "s",
+        'š' => "ʃ",
+        't' => "t",
+        'v' => "ʋ",
+        'z' => "z",
+        'ž' => "ʒ",
+        _ => "",
+    }
+}
+
+fn is_front_vowel(c: char) -> bool {
+    ['e', 'ę', 'ė', 'i', 'į', 'y'].contains(&c)
+}
+
+/// Splits a word, with or without accent marks, into its letters and the IPA for each of them.
+///
+/// Each item is the byte range of one or more graphemes in `word` and the sounds they stand for:
+///
+/// - `ch`, `dz` and `dž` stand for a single sound, so they get a single range
+/// - diphthongs (`ai`, `au`, `ei`, `eu`, `ie`, `ui`, `uo`) also get a single range
+/// - an `i` that only softens the consonant before it stands for no sound of its own, so its IPA is
+///   empty and the consonant before it gets a `ʲ` instead, as consonants before front vowels do
+///
+/// The segment with the stress mark starts with `ˈ`. The transcription is a broad, rule-based one
+/// of standard Lithuanian: it doesn't cover assimilation between consonants or loanwords that
+/// break the usual spelling rules.
+///
+/// # Examples
+///
+/// ```
+/// use lithuanian_phonology::align_graphemes_to_phonemes;
+///
+/// let aligned = align_graphemes_to_phonemes("džiaũgsmas");
+/// assert_eq!(aligned[0], (0..3, String::from("d͡ʒʲ")));
+/// assert_eq!(aligned[1], (3..4, String::new()));
+/// assert_eq!(aligned[2], (4..7, String::from("ˈɐʊ")));
+/// ```
+pub fn align_graphemes_to_phonemes(word: &str) -> Vec<(Range<usize>, String)> {
+    let letters = letters(word);
+    let bases: String = letters.iter().map(|l| l.base).collect();
+    let nuclei = syllables::nuclei(&bases);
+
+    let mut aligned = Vec::new();
+    let mut i = 0;
+    while i < letters.len() {
+        let letter = &letters[i];
+        let next = letters.get(i + 1);
+        let (len, sound) = if let Some(nucleus) = nuclei.iter().find(|n| n.start == i) {
+            match (nucleus.kind, next) {
+                (NucleusKind::Diphthong, Some(next)) => {
+                    (2, diphthong(letter.base, next.base).to_string())
+                }
+                _ => (1, vowel(letter.base, letter.stress).to_string()),
+            }
+        } else if syllables::is_vowel(letter.base) {
+            // a softening i
+            (1, String::new())
+        } else {
+            let digraph = match (letter.base, next.map(|n| n.base)) {
+                ('c', Some('h')) => Some("x"),
+                ('d', Some('z')) => Some("d͡z"),
+                ('d', Some('ž')) => Some("d͡ʒ"),
+                _ => None,
+            };
+            let (len, mut sound) = match digraph {
+                Some(sound) => (2, sound.to_string()),
+                None => (1, consonant(letter.base).to_string()),
+            };
+            let palatalized = letters
+                .get(i + len)
+                .is_some_and(|following| is_front_vowel(following.base));
+            if palatalized && letter.base != 'j' && !sound.is_empty() {
+                sound.push('ʲ');
+            }
+            (len, sound)
+        };
+
+        let covered = &letters[i..(i + len).min(letters.len())];
+        let range = covered[0].range.start..covered[covered.len() - 1].range.end;
+        let sound = if covered.iter().any(|l| l.stress.is_some()) {
+            format!("ˈ{sound}")
+        } else {
+            sound
+        };
+        aligned.push((range, sound));
+        i += len;
+    }
+    aligned
+}
+
+/// Transcribes a word, with or without accent marks, into IPA. This is
+/// [`align_graphemes_to_phonemes`] without the alignment.
+///
+/// # Examples
+///
+/// ```
+/// use lithuanian_phonology::transcribe;
+///
+/// assert_eq!(transcribe("žõdį"), "ʒˈoːdʲiː");
+/// assert_eq!(transcribe("kelias"), "kʲɛlʲɐs");
+/// ```
+pub fn transcribe(word: &str) -> String {
+    align_graphemes_to_phonemes(word)
+        .into_iter()
+        .map(|(_, sound)| sound)
+        .collect()
+}
+
+// The vowels of IPA, which make up syllable nuclei when they are next to each other.
+const IPA_VOWELS: &str = "aɐæeɛəiɪɨoɔuʊyʏøœɑɒʌɤɯɵɘɜɞʉ";
+// Tone marks on the stressed vowel: falling for the acute, rising for the circumflex.
+const FALLING: char = '\u{0302}';
+const RISING: char = '\u{030C}';
+
+/// One syllable nucleus of an IPA transcription.
+#[derive(Default)]
+struct IpaNucleus {
+    vowels: usize,
+    long: bool,
+    tone: Option<u8>,
+}
+
+/// Takes a word and an IPA transcription of it with a primary stress mark (`ˈ`), and returns the
+/// word with the matching Lithuanian accent mark, for corpora that have IPA but no orthographic
+/// stress. This is the other direction of [`transcribe`].
+///
+/// The two are aligned by syllable: the nuclei of the IPA (runs of vowel symbols, so a diphthong
+/// like `ɐʊ` or a vowel with a length mark is one) are counted up to the stress mark, and the
+/// stress goes on the word's nucleus with the same number, the way [`crate::accentuate_forced`]
+/// counts them. The stress mark can come before the stressed syllable or right before its vowel.
+/// The stress type comes from the stressed nucleus:
+///
+/// - a falling tone mark (`â`, U+0302) is an acute, and a rising one (`ǎ`, U+030C) a circumflex,
+///   as Lithuanian dictionaries transcribe them
+/// - a short vowel without a tone mark is a short stress
+/// - a long vowel or diphthong without a tone mark is a [`PhonologyError::IpaMismatch`] error,
+///   since IPA without tones doesn't say which of the two it is
+///
+/// Because only nuclei are counted, digraphs (`ch`, `dz`, `dž`), the softening `i` and consonant
+/// clusters don't get in the way, but the two sides have to agree on the number of syllables: a
+/// transcription that merges two vowels the spelling keeps apart, like the `ao` of `aorta`
+/// written without a syllable break (`.`), or one with a different number of syllables for any
+/// other reason, is an [`PhonologyError::IpaMismatch`] error rather than a guess.
+///
+/// # Examples
+///
+/// ```
+/// use lithuanian_phonology::stress_from_ipa;
+///
+/// assert_eq!(stress_from_ipa("žodis", "ˈʒǒːdʲɪs").unwrap(), "žõdis");
+/// assert_eq!(stress_from_ipa("vyras", "ˈʋʲîːrɐs").unwrap(), "výras");
+/// assert_eq!(stress_from_ipa("gera", "ɡʲɛˈrɐ").unwrap(), "gerà");
+/// assert!(stress_from_ipa("žodis", "ʒoːdʲɪs").is_err());
+/// ```
+pub fn stress_from_ipa(word: &str, ipa: &str) -> Result<String, PhonologyError> {
+    let mismatch = |reason: String| PhonologyError::IpaMismatch {
+        word: word.to_string(),
+        ipa: ipa.to_string(),
+        reason,
+    };
+    let mut nuclei: Vec<IpaNucleus> = Vec::new();
+    let mut in_nucleus = false;
+    let mut stressed = None;
+    for c in ipa.nfd() {
+        if IPA_VOWELS.contains(c) {
+            if !in_nucleus {
+                nuclei.push(IpaNucleus::default());
+                in_nucleus = true;
+            }
+            if let Some(nucleus) = nuclei.last_mut() {
+                nucleus.vowels += 1;
+            }
+            continue;
+        }
+        let nucleus = nuclei.last_mut().filter(|_| in_nucleus);
+        match (c, nucleus) {
+            ('ː' | 'ˑ', Some(nucleus)) => nucleus.long = true,
+            (FALLING, Some(nucleus)) => nucleus.tone = Some(1),
+            (RISING, Some(nucleus)) => nucleus.tone = Some(2),
+            // other marks on a vowel, like the ̯ of a non-syllabic one
+            (c, Some(_)) if is_combining_mark(c) => {}
+            ('ˈ', _) if stressed.is_some() => {
+                return Err(mismatch(String::from(
+                    "it has more than one primary stress mark",
+                )))
+            }
+            ('ˈ', _) => {
+                stressed = Some(nuclei.len());
+                in_nucleus = false;
+            }
+            _ => in_nucleus = false,
+        }
+    }
+
+    let stressed =
+        stressed.ok_or_else(|| mismatch(String::from("it has no primary stress mark")))?;
+    let word_nuclei = syllables::nuclei(word).len();
+    if nuclei.len() != word_nuclei {
+        return Err(mismatch(format!(
+            "it has {} syllables and the word {word_nuclei}",
+            nuclei.len()
+        )));
+    }
+    let nucleus = nuclei
+        .get(stressed)
+        .ok_or_else(|| mismatch(String::from("the stress mark isn't before a syllable")))?;
+    let stress_type = match nucleus.tone {
+        Some(tone) => tone,
+        None if nucleus.vowels == 1 && !nucleus.long => 0,
+        None => {
+            return Err(mismatch(String::from(
+                "the stressed syllable is long, and has no tone mark to tell an acute from a \
+                 circumflex",
+            )))
+        }
+    };
+    accentuate_forced(word, stressed, stress_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sounds(word: &str) -> Vec<(&str, String)> {
+        align_graphemes_to_phonemes(word)
+            .into_iter()
+            .map(|(range, sound)| (&word[range], sound))
+            .collect()
+    }
+
+    #[test]
+    fn digraphs_are_single_segments() {
+        assert_eq!(
+            sounds("choras"),
+            [
+                ("ch", String::from("x")),
+                ("o", String::from("oː")),
+                ("r", String::from("r")),
+                ("a", String::from("ɐ")),
+                ("s", String::from("s")),
+            ]
+        );
+        assert_eq!(sounds("dzūkas")[0], ("dz", String::from("d͡z")));
+    }
+
+    #[test]
+    fn softening_i_has_no_sound() {
+        assert_eq!(
+            sounds("kelio"),
+            [
+                ("k", String::from("kʲ")),
+                ("e", String::from("ɛ")),
+                ("l", String::from("lʲ")),
+                ("i", String::new()),
+                ("o", String::from("oː")),
+            ]
+        );
+    }
+
+    #[test]
+    fn stress_mark_position() {
+        assert_eq!(sounds("gẽra")[1], ("ẽ", String::from("ˈæː")));
+        assert_eq!(sounds("vil̃kas")[2], ("l̃", String::from("ˈl")));
+        assert_eq!(sounds("Ą̃žuolas")[0], ("Ą̃", String::from("ˈaː")));
+        assert_eq!(sounds("diẽna")[1], ("iẽ", String::from("ˈiɛ")));
+    }
+
+    #[test]
+    fn stress_from_transcriptions() {
+        let stress = stress_from_ipa;
+        assert_eq!(stress("diena", "ˈdʲiɛ̌nɐ").unwrap(), "diẽna");
+        assert_eq!(stress("diena", "dʲiɛˈnɐ").unwrap(), "dienà");
+        assert_eq!(stress("vilkas", "ˈʋʲɪ̌lkɐs").unwrap(), "vil̃kas");
+        assert_eq!(stress("kelio", "ˈkʲæ̌ːlʲoː").unwrap(), "kẽlio");
+        assert_eq!(stress("choras", "ˈxôːrɐs").unwrap(), "chóras");
+        // the stress mark right before the vowel, as transcribe writes it
+        assert_eq!(stress("gera", &transcribe("gerà")).unwrap(), "gerà");
+        assert_eq!(stress("katu", "kɐˈtʊ").unwrap(), "katu\u{0300}");
+
+        let reason = |word, ipa| match stress(word, ipa) {
+            Err(PhonologyError::IpaMismatch { reason, .. }) => reason,
+            other => panic!("{other:?}"),
+        };
+        assert_eq!(
+            reason("diena", "ˈdʲiɛnɐ"),
+            "the stressed syllable is long, and has no tone mark to tell an acute from a circumflex"
+        );
+        assert_eq!(
+            reason("džiaugsmas", "d͡ʒʲɐʊ̌ɡsmɐs"),
+            "it has no primary stress mark"
+        );
+        assert_eq!(
+            reason("gera", "ˈɡʲæ̌ːrɐ.ɐ"),
+            "it has 3 syllables and the word 2"
+        );
+        assert_eq!(
+            reason("gera", "ˈɡʲæ̌ːˈrɐ"),
+            "it has more than one primary stress mark"
+        );
+        assert_eq!(
+            reason("gera", "ɡʲæ̌ːrɐˈ"),
+            "the stress mark isn't before a syllable"
+        );
+    }
+}
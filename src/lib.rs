@@ -1,113 +1,1885 @@
-use phf::phf_map;
-use std::{collections::HashMap, error::Error};
+//! Adds lithuanian accent marks to words using `phonology_engine`.
+//!
+//! Every function that needs the engine acquires the GIL itself with [`Python::with_gil`]. When the
+//! GIL is already held, for example inside a pyo3 extension, use the `_py` variants
+//! ([`get_accentuation_py`], [`accentuate_py`], [`get_all_accentuations_py`],
+//! [`get_all_accentuation_results_py`], [`get_stress_options_py`], [`get_transcription_py`] and
+//! [`process_raw_py`]) with the existing [`Python`] token instead. They never acquire or release
+//! the GIL, so they can be called from anywhere a `Python<'py>` is available. The engine itself
+//! runs Python code while the GIL is held, so it shouldn't be called from one of its own callbacks.
+//!
+//! Nothing in the library panics on what the engine returns. Missing or malformed fields, stress
+//! indices outside the word and stress types that can't be written are all returned as
+//! [`PhonologyError`]s (or, for the functions that return a plain word, leave it unchanged), so a
+//! new version of the engine or an unusual word can't abort the program using the crate.
+//!
+//! Capitals are kept where they are, so names like `Kazlauskas` (`Kazlaũskas`) and words written
+//! in capitals (`MAŽVÝDAS`) get their stress mark on the same letter as in lowercase, capital or
+//! not. What still goes wrong with names is finding their stress in the first place: a surname the
+//! engine's dictionary doesn't have gets no options at all, [`guess_stress_by_ending`] doesn't know
+//! surname suffixes like `-auskas` or `-ienė`, and foreign names with letters Lithuanian doesn't
+//! have, like the `ü` of `Müller`, can't be written with a stress mark on them.
 
-use pyo3::prelude::*;
+use phf::{phf_map, phf_ordered_map};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    io::{self, BufRead},
+    ops::Deref,
+    sync::{Mutex, PoisonError, RwLock},
+    time::{Duration, Instant},
+};
+use unicode_normalization::{
+    char::{canonical_combining_class, is_combining_mark},
+    UnicodeNormalization,
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+use pyo3::{prelude::*, sync::GILOnceCell};
+
+mod accent;
+mod adjective;
+mod analysis;
+mod annotate;
+mod ansi;
+mod ascii;
+mod compound;
+mod conllu;
+mod error;
+mod guess;
+mod ipa;
+mod markdown;
+mod mood;
+mod morph;
+#[cfg(feature = "offline")]
+mod offline;
+#[cfg(feature = "offline")]
+mod offline_format;
+#[cfg(feature = "tokio")]
+mod pool;
+mod prefix;
+mod pronunciation;
+mod raw;
+mod recording;
+mod resolver;
+mod ruby;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+mod ssml;
+mod stats;
+mod syllables;
+mod table;
+mod text;
+mod verb;
+mod verify;
+
+pub use accent::{
+    accent_paradigm, same_paradigm, same_paradigm_with, stress_mobility, stress_mobility_of_forms,
+    Mobility,
+};
+pub use adjective::{accentuate_adjective, accentuate_adjective_with, Degree, Gender};
+pub use analysis::{
+    analyze, analyze_with, stress_in_syllable, stress_in_syllable_with, StressAnalysis,
+    SyllablePosition,
+};
+pub use annotate::{
+    accentuate_at, accentuate_at_with, annotate_sentence, annotate_sentence_with, WordAnnotation,
+};
+pub use ansi::{to_ansi, AnsiOptions};
+pub use ascii::{from_ascii_lithuanian, AsciiScheme};
+pub use compound::{accentuate_compound, stress_units, StressUnit};
+pub use conllu::{to_conllu_misc, to_conllu_misc_with};
+pub use error::PhonologyError;
+pub use guess::{guess_stress_by_ending, guess_with_confidence};
+pub use ipa::{align_graphemes_to_phonemes, stress_from_ipa, transcribe};
+pub use markdown::{to_markdown, to_markdown_with};
+pub use mood::{accentuate_mood, accentuate_mood_with, Mood};
+pub use morph::{accentuate_analyzed, accentuate_analyzed_with, MorphAnalysis};
+#[cfg(feature = "offline")]
+pub use offline::{lookup_offline, OfflineTable};
+#[cfg(feature = "tokio")]
+pub use pool::AsyncEngine;
+pub use prefix::{accentuate_negated, accentuate_negated_with};
+pub use pronunciation::{
+    pronunciation, pronunciation_with, stress_from_end, stress_from_end_with, Pronunciation,
+};
+pub use raw::{process_raw, process_raw_py, segment, RawUnit, RawWord};
+pub use recording::{RecordingResolver, StressDictionary};
+pub use resolver::{check_environment, EnvInfo, PhonologyEngine, StressResolver};
+pub use ruby::{to_ruby_html, to_ruby_html_with, RubyOptions};
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteStressCache;
+pub use ssml::to_ssml;
+pub use stats::{
+    distinct_stress_patterns, distinct_stress_patterns_with, stress_type_histogram, text_report,
+    text_report_with, StressTypeHistogram, TextReport,
+};
+pub use syllables::{classify, LetterClass, PhonologyConfig};
+pub use table::{
+    build_tables, build_tables_with, full_paradigm, syncretic_cases, syncretic_cases_with,
+    DeclensionTable, FullParadigm, Number,
+};
+pub use verb::{accentuate_verb_forms, accentuate_verb_forms_with, verb_forms, VerbForms};
+pub use verify::{
+    audit_corpus, audit_corpus_with, verify, verify_with, AuditReport, Discrepancy, Verification,
+};
+
+/// One of the ways the engine thinks a word can be stressed, along with the grammatical form it
+/// belongs to.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StressOption {
+    /// The engine's stress type: 0 for short stress, 1 for acute and 2 for circumflex.
+    pub stress_type: u8,
+    /// Index (in chars) of the letter that carries the stress mark.
+    pub stressed_letter_index: usize,
+    /// Lithuanian name of the case, such as `Vardininkas`.
+    pub grammatical_case: Option<String>,
+    /// Lithuanian name of the number, such as `vienaskaita`.
+    pub number: Option<String>,
+    /// Lithuanian name of the gender, such as `vyriškoji`.
+    pub gender: Option<String>,
+    /// Every other text, number or boolean field the engine attached to the option, keyed by the
+    /// engine's name for it. Numbers and booleans are written like `0.75` and `true`.
+    pub details: HashMap<String, String>,
+}
+
+impl StressOption {
+    /// Whether the engine is sure about this option, as opposed to having guessed it for a word it
+    /// doesn't know. An option is a guess if it has a `guessed` detail that is `true`, or a
+    /// `confidence` detail below 0.5.
+    ///
+    /// As far as the crate knows, `phonology_engine` doesn't attach either of these, so for its
+    /// options this is always `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lithuanian_phonology::StressOption;
+    ///
+    /// let mut option = StressOption::default();
+    /// assert!(option.is_confident());
+    /// option.details.insert(String::from("confidence"), String::from("0.2"));
+    /// assert!(!option.is_confident());
+    /// ```
+    pub fn is_confident(&self) -> bool {
+        let guessed = self.details.get("guessed").is_some_and(|g| g == "true");
+        let unsure = self
+            .details
+            .get("confidence")
+            .and_then(|c| c.parse::<f64>().ok())
+            .is_some_and(|c| c < 0.5);
+        !guessed && !unsure
+    }
+
+    /// The mood of the verb form this option belongs to, from the engine's `mood` detail. `None`
+    /// for options without one, or with a name [`Mood::from_name`] doesn't know.
+    pub fn mood(&self) -> Option<Mood> {
+        self.details
+            .get("mood")
+            .and_then(|name| Mood::from_name(name))
+    }
+}
 
 /// Takes a word, an optional case and number, and returns it with lithuanian accent marks.
-/// If the case or number is left out, it will simply go with the first value it finds.
+/// If the case or number is left out, it will simply go with the first value it finds. The case
+/// can be given by its Lithuanian or english name (see [`Case::from_name`]), and one that isn't
+/// either is treated as left out.
 ///
 /// # Examples
 ///
-/// ```
+/// ```no_run
 /// use lithuanian_phonology::get_accentuation;
 ///
-/// assert_eq!(get_accentuation("gera", Some("Vardininkas"), None), String::from("gerà"));
-/// assert_eq!(get_accentuation("gera", Some("UNKNOWN"), None), String::from("gẽra"));
-/// assert_eq!(get_accentuation("žodį", Some("Galininkas"), Some("vienaskaita")), String::from("žõdį"));
+/// assert_eq!(get_accentuation("gera", Some("Vardininkas"), None).unwrap(), String::from("gerà"));
+/// assert_eq!(get_accentuation("gera", Some("Nominative"), None).unwrap(), String::from("gerà"));
+/// assert_eq!(get_accentuation("gera", Some("UNKNOWN"), None).unwrap(), String::from("gẽra"));
+/// assert_eq!(get_accentuation("žodį", Some("Galininkas"), Some("vienaskaita")).unwrap(), String::from("žõdį"));
 /// ```
 pub fn get_accentuation(
     word: &str,
     case: Option<&str>,
     number: Option<&str>,
-) -> Result<String, Box<dyn Error>> {
+) -> Result<Accented, PhonologyError> {
+    Python::with_gil(|py| get_accentuation_py(py, word, case, number))
+}
+
+/// Like [`get_accentuation`], but uses a GIL that is already held.
+pub fn get_accentuation_py(
+    py: Python<'_>,
+    word: &str,
+    case: Option<&str>,
+    number: Option<&str>,
+) -> Result<Accented, PhonologyError> {
+    accentuate_py(py, word, case, number, &AccentuationOptions::default())
+        .map(|stressed| Accented::new(word, stressed))
+}
+
+/// Takes a word and returns it with lithuanian accent marks in its dictionary form, for when no
+/// particular case is needed. The stress option used is, in order:
+///
+/// 1. the nominative singular
+/// 2. the nominative in any number, for words that only have a plural like `durys`
+/// 3. the engine's first option, for words that don't decline
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::get_default_accentuation;
+///
+/// assert_eq!(get_default_accentuation("gera").unwrap(), "gerà");
+/// ```
+pub fn get_default_accentuation(word: &str) -> Result<Accented, PhonologyError> {
     Python::with_gil(|py| {
-        let phonology = PyModule::import(py, "phonology_engine")?;
-        let pe = phonology.getattr("PhonologyEngine")?.call0()?;
-
-        let version: Vec<HashMap<String, PyObject>> = pe
-            .getattr("process")?
-            .call((word,), None)?
-            .getattr("__next__")?
-            .call0()?
-            .get_item(0)?
-            .get_item(0)?
-            .get_item("stress_options")?
-            .get_item("decoded_options")?
-            .extract()?;
-
-        for i in version {
-            let mut meets_criteria = true;
-            if let Some(c) = case {
-                let current_case: &str = i.get("grammatical_case").unwrap().extract(py)?;
-                if current_case != c {
-                    meets_criteria = false;
+        let options = get_stress_options_py(py, word)?;
+        let option = default_option(&options).ok_or_else(|| PhonologyError::CaseNotFound {
+            word: word.to_string(),
+            case: None,
+            number: None,
+        })?;
+        stressed_word(word, option.clone(), false, &AccentuationOptions::default())
+            .map(|stressed| Accented::new(word, stressed))
+    })
+}
+
+fn default_option(options: &[StressOption]) -> Option<&StressOption> {
+    let nominative = CASE_NAMES.get("nominative").copied();
+    find_option(
+        options,
+        nominative,
+        Some(Number::Singular.lithuanian_name()),
+    )
+    .or_else(|| find_option(options, nominative, None))
+    .or(options.first())
+}
+
+/// Settings that change how [`accentuate`] picks a stress option.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccentuationOptions {
+    /// If there is no option for the exact case and number, use the closest one with the same
+    /// case instead of failing: first one without a number, then one with any number. Off by
+    /// default.
+    pub fallback: bool,
+    /// Fail when the requested case isn't the english or Lithuanian name of one of the seven cases,
+    /// such as the `UNKNOWN` that [`get_case_name`] returns for names it doesn't know. When this is
+    /// off (the default), an unrecognized case is ignored and the engine's first option for the
+    /// word is used instead.
+    pub strict_cases: bool,
+    /// Use the nominative when the vocative is requested but the engine has no vocative for the
+    /// word, which is common since many nouns don't have a distinct one. Off by default, in which
+    /// case this is a [`PhonologyError::NoVocative`].
+    pub vocative_fallback: bool,
+    /// Where to put the stress mark: on the stressed letter (the default) or as a separate mark
+    /// before it.
+    pub mark_placement: MarkPlacement,
+    /// Take punctuation at the start and end of the word, like the comma in `gera,` or the quotes
+    /// in `„gera“`, off before looking it up, and put it back around the accented word. Off by
+    /// default, in which case the engine gets the punctuation too. The stress option still counts
+    /// its letters from the start of the word without the punctuation.
+    pub strip_punctuation: bool,
+    /// Write a short stress as the letter followed by a combining grave (U+0300), like the acute
+    /// and tilde are on letters that have no precomposed form, instead of a precomposed letter
+    /// like `à` (U+00E0). The two look the same and are equal after NFD normalization; this is for
+    /// text that should use one mechanism for every stress mark. Off by default.
+    pub combining_grave: bool,
+    /// Write a circumflex on a long vowel with a mark of its own (`ą`, `ę`, `ė`, `į`, `ų` and
+    /// `ū`) as the letter followed by its base vowel with the tilde, like `ūũ` for `ū̃`, instead of
+    /// the letter with a combining tilde (U+0303) stacked over it. Many fonts draw the stacked
+    /// marks on top of each other or not at all, while every letter of the doubled form has a
+    /// precomposed codepoint; the doubling also spells out that the vowel is long. Off by default.
+    pub doubled_long_circumflex: bool,
+}
+
+/// A word with its accent marks, along with the stress option that was used to place them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StressedWord {
+    /// The word with its accent marks.
+    pub accented: String,
+    /// Just the stress mark that was added: U+0300 (grave) for a short stress, U+0301 (acute) or
+    /// U+0303 (tilde) for a circumflex, or with [`MarkPlacement::Preceding`] the separate mark from
+    /// [`MarkPlacement::preceding_mark`]. Empty if no mark could be placed.
+    pub mark: String,
+    /// The stress option the accent marks came from.
+    pub option: StressOption,
+    /// Whether the option is only the closest match to the requested case and number, because
+    /// [`AccentuationOptions::fallback`] was on and there was no exact match.
+    pub fell_back: bool,
+    /// The case of the option, if it has one, whichever name it was requested by.
+    /// [`Case::lithuanian_name`] gives its label, such as `Vardininkas`.
+    pub case: Option<Case>,
+    /// Whether the engine is sure about the option. See [`StressOption::is_confident`].
+    pub confident: bool,
+}
+
+/// A word with its accent marks that remembers where its stress is, as returned by
+/// [`get_accentuation`]. It derefs to the accented text, so it can be printed, compared and sliced
+/// like a `&str`, and [`Accented::into_string`] gives the text on its own.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::get_accentuation;
+///
+/// let accented = get_accentuation("žodį", Some("Galininkas"), None).unwrap();
+/// assert_eq!(accented, "žõdį");
+/// assert_eq!((accented.base(), accented.stressed_letter_index()), ("žodį", 1));
+/// assert_eq!(accented.stress_type(), 2);
+/// assert!(accented.starts_with('ž'));
+/// println!("{accented}");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Accented {
+    accented: String,
+    base: String,
+    stressed_letter_index: usize,
+    stress_type: u8,
+}
+
+impl Accented {
+    fn new(base: &str, stressed: StressedWord) -> Self {
+        Self {
+            stressed_letter_index: effective_index(base, &stressed.option),
+            stress_type: stressed.option.stress_type,
+            accented: stressed.accented,
+            base: base.to_string(),
+        }
+    }
+
+    /// The word with its accent marks.
+    pub fn as_str(&self) -> &str {
+        &self.accented
+    }
+
+    /// The word as it was given, without the accent marks.
+    pub fn base(&self) -> &str {
+        &self.base
+    }
+
+    /// Index (in chars) of the letter of [`Accented::base`] that carries the stress mark. This is
+    /// where the mark was written, which isn't always the engine's index (see
+    /// [`StressOption::stressed_letter_index`]) when the engine puts the stress on an `i` that
+    /// only softens the consonant before it.
+    pub fn stressed_letter_index(&self) -> usize {
+        self.stressed_letter_index
+    }
+
+    /// The stress type: 0 for short stress, 1 for acute and 2 for circumflex.
+    pub fn stress_type(&self) -> u8 {
+        self.stress_type
+    }
+
+    /// The word with its accent marks, without the rest.
+    pub fn into_string(self) -> String {
+        self.accented
+    }
+}
+
+impl Deref for Accented {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.accented
+    }
+}
+
+impl AsRef<str> for Accented {
+    fn as_ref(&self) -> &str {
+        &self.accented
+    }
+}
+
+impl fmt::Display for Accented {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.accented)
+    }
+}
+
+impl From<Accented> for String {
+    fn from(accented: Accented) -> Self {
+        accented.accented
+    }
+}
+
+impl PartialEq<str> for Accented {
+    fn eq(&self, other: &str) -> bool {
+        self.accented == other
+    }
+}
+
+impl PartialEq<&str> for Accented {
+    fn eq(&self, other: &&str) -> bool {
+        self.accented == *other
+    }
+}
+
+impl PartialEq<String> for Accented {
+    fn eq(&self, other: &String) -> bool {
+        &self.accented == other
+    }
+}
+
+/// Like [`get_accentuation`], but with [`AccentuationOptions`] and a result that says which stress
+/// option was used.
+///
+/// Words that don't inflect, like adverbs (`gerai`, `geriau`, `geriausiai`), conjunctions and
+/// particles, come back from the engine without a case or number. For them the case and number
+/// are ignored, and the engine's first option is used whatever was asked for.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::{accentuate, AccentuationOptions};
+///
+/// let options = AccentuationOptions {
+///     fallback: true,
+///     ..Default::default()
+/// };
+/// let stressed = accentuate("žodį", Some("Galininkas"), Some("daugiskaita"), &options).unwrap();
+/// if stressed.fell_back {
+///     println!("{} is only an approximation", stressed.accented);
+/// }
+/// ```
+pub fn accentuate(
+    word: &str,
+    case: Option<&str>,
+    number: Option<&str>,
+    options: &AccentuationOptions,
+) -> Result<StressedWord, PhonologyError> {
+    Python::with_gil(|py| accentuate_py(py, word, case, number, options))
+}
+
+/// Like [`accentuate`], but uses a GIL that is already held.
+pub fn accentuate_py(
+    py: Python<'_>,
+    word: &str,
+    case: Option<&str>,
+    number: Option<&str>,
+    options: &AccentuationOptions,
+) -> Result<StressedWord, PhonologyError> {
+    with_punctuation(word, options, |word| {
+        let stress_options = get_stress_options_py(py, word)?;
+        let (option, fell_back) = select_option(word, &stress_options, case, number, options)?;
+        stressed_word(word, option.clone(), fell_back, options)
+    })
+}
+
+/// Like [`accentuate`], but gets the stress options from `resolver` instead of the engine.
+pub fn accentuate_with(
+    resolver: &impl StressResolver,
+    word: &str,
+    case: Option<&str>,
+    number: Option<&str>,
+    options: &AccentuationOptions,
+) -> Result<StressedWord, PhonologyError> {
+    with_punctuation(word, options, |word| {
+        let stress_options = resolver.stress_options(word)?;
+        let (option, fell_back) = select_option(word, &stress_options, case, number, options)?;
+        stressed_word(word, option.clone(), fell_back, options)
+    })
+}
+
+// Accentuates the word without its surrounding punctuation if
+// `AccentuationOptions::strip_punctuation` is on, and puts the punctuation back afterwards.
+fn with_punctuation(
+    word: &str,
+    options: &AccentuationOptions,
+    accentuate: impl FnOnce(&str) -> Result<StressedWord, PhonologyError>,
+) -> Result<StressedWord, PhonologyError> {
+    if !options.strip_punctuation {
+        return accentuate(word);
+    }
+    let start = word.find(text::is_word_char).unwrap_or(word.len());
+    let end = word
+        .char_indices()
+        .rev()
+        .find(|&(_, c)| text::is_word_char(c))
+        .map_or(start, |(i, c)| i + c.len_utf8());
+    if start >= end {
+        return accentuate(word);
+    }
+    let mut stressed = accentuate(&word[start..end])?;
+    stressed.accented = format!("{}{}{}", &word[..start], stressed.accented, &word[end..]);
+    Ok(stressed)
+}
+
+pub(crate) fn stressed_word(
+    word: &str,
+    option: StressOption,
+    fell_back: bool,
+    options: &AccentuationOptions,
+) -> Result<StressedWord, PhonologyError> {
+    let placement = options.mark_placement;
+    let accented = match placement {
+        MarkPlacement::Over if options.combining_grave && option.stress_type == 0 => {
+            decompose_graves(&try_stress_with_option(word, &option)?)
+        }
+        MarkPlacement::Over if options.doubled_long_circumflex && option.stress_type == 2 => {
+            double_long_circumflexes(&try_stress_with_option(word, &option)?)
+        }
+        MarkPlacement::Over => try_stress_with_option(word, &option)?,
+        MarkPlacement::Preceding => place_stress(
+            word,
+            option.stress_type,
+            effective_index(word, &option),
+            placement,
+        )?,
+    };
+    let mark = match placement {
+        _ if accented == word => None,
+        MarkPlacement::Over => stress_mark(option.stress_type),
+        MarkPlacement::Preceding => MarkPlacement::preceding_mark(option.stress_type),
+    };
+    Ok(StressedWord {
+        accented,
+        mark: mark.unwrap_or_default().to_string(),
+        case: option.grammatical_case.as_deref().and_then(Case::from_name),
+        confident: option.is_confident(),
+        option,
+        fell_back,
+    })
+}
+
+/// Picks the stress option for a case and number, and whether it's only a fallback.
+pub(crate) fn select_option<'a>(
+    word: &str,
+    stress_options: &'a [StressOption],
+    case: Option<&str>,
+    number: Option<&str>,
+    options: &AccentuationOptions,
+) -> Result<(&'a StressOption, bool), PhonologyError> {
+    let case = requested_case(case, options);
+    if let Some(option) = find_option(stress_options, case, number) {
+        return Ok((option, false));
+    }
+    // Words that don't inflect, like adverbs, have neither a case nor a number, so whatever was
+    // requested they have only the one form.
+    let uninflected = stress_options
+        .iter()
+        .all(|option| option.grammatical_case.is_none() && option.number.is_none());
+    if let Some(option) = stress_options.first().filter(|_| uninflected) {
+        return Ok((option, false));
+    }
+    if options.fallback {
+        if let Some(option) = find_closest_option(stress_options, case) {
+            return Ok((option, true));
+        }
+    }
+
+    let vocative = Case::Vocative.lithuanian_name();
+    if case == Some(vocative) {
+        if !options.vocative_fallback {
+            return Err(PhonologyError::NoVocative {
+                word: word.to_string(),
+            });
+        }
+        let nominative = Some(Case::Nominative.lithuanian_name());
+        if let Some(option) = find_option(stress_options, nominative, number) {
+            return Ok((option, true));
+        }
+    }
+    Err(PhonologyError::CaseNotFound {
+        word: word.to_string(),
+        case: case.map(String::from),
+        number: number.map(String::from),
+    })
+}
+
+/// The results of accentuating a list of words with [`accentuate_batch`].
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct BatchResult {
+    /// Every word in the order it was given, with its result.
+    pub items: Vec<(String, Result<StressedWord, PhonologyError>)>,
+    /// How long the whole batch took.
+    pub elapsed: Duration,
+    /// How many words were repeats of an earlier word in the batch, and so didn't need the engine.
+    pub cache_hits: usize,
+}
+
+/// Accentuates a list of words with the same case, number and [`AccentuationOptions`], holding
+/// the GIL for the whole batch. A word that fails doesn't stop the others. Words that appear more
+/// than once are only looked up once.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::{accentuate_batch, AccentuationOptions};
+///
+/// let result = accentuate_batch(&["žodį", "namą"], Some("Galininkas"), None, &AccentuationOptions::default());
+/// for (word, stressed) in &result.items {
+///     match stressed {
+///         Ok(stressed) => println!("{word}: {}", stressed.accented),
+///         Err(e) => eprintln!("{e}"),
+///     }
+/// }
+/// println!("took {:?}", result.elapsed);
+/// ```
+pub fn accentuate_batch(
+    words: &[&str],
+    case: Option<&str>,
+    number: Option<&str>,
+    options: &AccentuationOptions,
+) -> BatchResult {
+    let start = Instant::now();
+    let mut cache: HashMap<&str, Result<StressedWord, PhonologyError>> = HashMap::new();
+    let mut cache_hits = 0;
+
+    let items = Python::with_gil(|py| {
+        words
+            .iter()
+            .map(|&word| {
+                if let Some(stressed) = cache.get(word) {
+                    cache_hits += 1;
+                    return (word.to_string(), stressed.clone());
+                }
+                let stressed = accentuate_py(py, word, case, number, options);
+                cache.insert(word, stressed.clone());
+                (word.to_string(), stressed)
+            })
+            .collect()
+    });
+
+    BatchResult {
+        items,
+        elapsed: start.elapsed(),
+        cache_hits,
+    }
+}
+
+/// Accentuates every line of `reader` as a word, one line at a time, so that files that don't fit
+/// in memory can be processed. Whitespace around each word is trimmed and empty lines are skipped.
+/// Reading stops at the first I/O error, which is the last item.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::{fs::File, io::BufReader};
+///
+/// use lithuanian_phonology::{accentuate_lines, AccentuationOptions};
+///
+/// let file = BufReader::new(File::open("words.txt").unwrap());
+/// let options = AccentuationOptions::default();
+/// for line in accentuate_lines(file, Some("Kilmininkas"), None, &options) {
+///     let (word, stressed) = line.unwrap();
+///     println!("{word}: {:?}", stressed.map(|s| s.accented));
+/// }
+/// ```
+pub fn accentuate_lines<'a, R: BufRead + 'a>(
+    reader: R,
+    case: Option<&'a str>,
+    number: Option<&'a str>,
+    options: &'a AccentuationOptions,
+) -> impl Iterator<Item = io::Result<(String, Result<StressedWord, PhonologyError>)>> + 'a {
+    nonempty_lines(reader).map(move |line| {
+        let word = line?.trim().to_string();
+        let stressed = accentuate(&word, case, number, options);
+        Ok((word, stressed))
+    })
+}
+
+/// Like [`accentuate_lines`], but each line is a word and the case to stress it for, separated by
+/// a tab, like `žodį\tAccusative`. The case can be an English or Lithuanian name, or one added
+/// with [`register_case`]. Lines without a tab are taken to be just a word, and get the engine's
+/// first option; names that aren't cases are handled as [`AccentuationOptions::strict_cases`]
+/// says, so set it to have them fail instead.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::{accentuate_pairs, AccentuationOptions};
+///
+/// let pairs = "žodį\tAccusative\nžodžio\tKilmininkas\n".as_bytes();
+/// let options = AccentuationOptions {
+///     strict_cases: true,
+///     ..Default::default()
+/// };
+/// let accented: Vec<_> = accentuate_pairs(pairs, None, &options)
+///     .map(|line| line.unwrap().1.unwrap().accented)
+///     .collect();
+/// assert_eq!(accented, ["žõdį", "žõdžio"]);
+/// ```
+pub fn accentuate_pairs<'a, R: BufRead + 'a>(
+    reader: R,
+    number: Option<&'a str>,
+    options: &'a AccentuationOptions,
+) -> impl Iterator<Item = io::Result<(String, Result<StressedWord, PhonologyError>)>> + 'a {
+    nonempty_lines(reader).map(move |line| {
+        let line = line?;
+        let (word, case) = match line.split_once('\t') {
+            Some((word, case)) => (word.trim(), Some(case.trim())),
+            None => (line.trim(), None),
+        };
+        let stressed = accentuate(word, case, number, options);
+        Ok((word.to_string(), stressed))
+    })
+}
+
+// The lines of `reader` that aren't blank, stopping after the first I/O error.
+fn nonempty_lines(reader: impl BufRead) -> impl Iterator<Item = io::Result<String>> {
+    let mut failed = false;
+    reader
+        .lines()
+        .map_while(move |line| {
+            if failed {
+                return None;
+            }
+            failed = line.is_err();
+            Some(line)
+        })
+        .filter(|line| !line.as_ref().is_ok_and(|line| line.trim().is_empty()))
+}
+
+/// Takes a word and a MULTEXT-East style morphological tag, and returns the word with lithuanian
+/// accent marks for the case and number the tag describes.
+///
+/// Only noun tags are supported. These are positional: `N`, then the type (`c`ommon or
+/// `p`roper), gender (`m`, `f` or `-`), number (`s`ingular, `p`lural or `d`ual) and case
+/// (`n`ominative, `g`enitive, `d`ative, `a`ccusative, `i`nstrumental, `l`ocative or
+/// `v`ocative). Anything after the case is ignored, and a `-` for the number matches any number.
+///
+/// The engine can't inflect words, so the word has to already be in the form the tag describes;
+/// the tag only picks which of its readings gets stressed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::accentuate_by_tag;
+///
+/// assert_eq!(accentuate_by_tag("žodį", "Ncmsan").unwrap(), "žõdį");
+/// assert!(accentuate_by_tag("žodį", "Vmip3s").is_err());
+/// ```
+pub fn accentuate_by_tag(word: &str, tag: &str) -> Result<Accented, PhonologyError> {
+    let (case, number) = parse_noun_tag(tag).ok_or_else(|| PhonologyError::UnsupportedTag {
+        tag: tag.to_string(),
+    })?;
+    get_accentuation(word, Some(case), number)
+}
+
+fn parse_noun_tag(tag: &str) -> Option<(&'static str, Option<&'static str>)> {
+    let mut positions = tag.chars();
+    if positions.next()? != 'N' {
+        return None;
+    }
+    let _noun_type = positions.next()?;
+    let _gender = positions.next()?;
+    let number = match positions.next()? {
+        's' => Some("vienaskaita"),
+        'p' => Some("daugiskaita"),
+        'd' => Some("dviskaita"),
+        '-' => None,
+        _ => return None,
+    };
+    let case = match positions.next()? {
+        'n' => "nominative",
+        'g' => "genitive",
+        'd' => "dative",
+        'a' => "accusative",
+        'i' => "instrumental",
+        'l' => "locative",
+        'v' => "vocative",
+        _ => return None,
+    };
+    Some((CASE_NAMES.get(case)?, number))
+}
+
+/// Takes a word and puts a stress mark on one of its syllables, ignoring what the engine says.
+/// `nucleus_ordinal` counts the word's syllable nuclei (vowels, diphthongs and mixed diphthongs
+/// like `il`) from 0, and `stress_type` uses the engine's numbering: 0 for short stress, 1 for
+/// acute and 2 for circumflex. Circumflexes on diphthongs go on their second letter, like in
+/// `laũkas` and `vil̃kas`.
+///
+/// # Examples
+///
+/// ```
+/// use lithuanian_phonology::accentuate_forced;
+///
+/// assert_eq!(accentuate_forced("gera", 0, 2).unwrap(), "gẽra");
+/// assert_eq!(accentuate_forced("gera", 1, 0).unwrap(), "gerà");
+/// assert_eq!(accentuate_forced("vilkas", 0, 2).unwrap(), "vil̃kas");
+/// assert!(accentuate_forced("gera", 2, 0).is_err());
+/// ```
+pub fn accentuate_forced(
+    word: &str,
+    nucleus_ordinal: usize,
+    stress_type: u8,
+) -> Result<String, PhonologyError> {
+    PhonologyConfig::standard().accentuate_forced(word, nucleus_ordinal, stress_type)
+}
+
+// Stresses one of `nuclei`, the nuclei of `word` as some inventory finds them.
+pub(crate) fn accentuate_nucleus(
+    word: &str,
+    nuclei: &[syllables::Nucleus],
+    nucleus_ordinal: usize,
+    stress_type: u8,
+) -> Result<String, PhonologyError> {
+    let nucleus = nuclei
+        .get(nucleus_ordinal)
+        .ok_or_else(|| PhonologyError::NucleusOutOfRange {
+            word: word.to_string(),
+            ordinal: nucleus_ordinal,
+            nuclei: nuclei.len(),
+        })?;
+    let index = nucleus.stressed_letter_index(stress_type);
+
+    let letter = word.chars().nth(index).unwrap_or_default();
+    let lower = letter.to_lowercase().next().unwrap_or(letter);
+    if !stress_map(stress_type).is_some_and(|map| map.contains_key(&lower)) {
+        return Err(PhonologyError::UnsupportedStress {
+            word: word.to_string(),
+            letter,
+            stress_type,
+        });
+    }
+    create_stresed_word(word, stress_type, index)
+}
+
+/// How many syllable nuclei a word has, which is how many syllables it has and how many choices of
+/// `nucleus_ordinal` [`accentuate_forced`] accepts: a nucleus is a vowel, a diphthong or a mixed
+/// diphthong like `il`, and an `i` that only softens the consonant before it (as in `kelio`)
+/// isn't one. Accent marks on the word are ignored, so `gerà` has two, like `gera`.
+///
+/// # Examples
+///
+/// ```
+/// use lithuanian_phonology::nucleus_count;
+///
+/// assert_eq!(nucleus_count("vilkas"), 2);
+/// assert_eq!(nucleus_count("kelio"), 2);
+/// assert_eq!(nucleus_count("Aukštaitija"), 4);
+/// assert_eq!(nucleus_count("brrr"), 0);
+/// assert_eq!(nucleus_count("gerà"), 2);
+/// ```
+pub fn nucleus_count(word: &str) -> usize {
+    PhonologyConfig::standard().nucleus_count(word)
+}
+
+/// Splits a word into its syllables, one for each nucleus (see [`nucleus_count`]). The consonants
+/// between two nuclei are split the way Lithuanian dictionaries usually split them: one consonant
+/// starts the next syllable and of several only the last does, except that a consonant followed by
+/// `r`, `l`, `v` or `j` goes with it and `ch`, `dz` and `dž` aren't split. A word without nuclei is
+/// one syllable. A word can be split with its accent marks, which stay on their letters, as long as
+/// they are written over the letters rather than with [`MarkPlacement::Preceding`]. To split words
+/// with other vowels or diphthongs, see [`PhonologyConfig`].
+///
+/// # Examples
+///
+/// ```
+/// use lithuanian_phonology::syllabify;
+///
+/// assert_eq!(syllabify("pakrantė"), ["pa", "kran", "tė"]);
+/// assert_eq!(syllabify("kelias"), ["ke", "lias"]);
+/// assert_eq!(syllabify("brrr"), ["brrr"]);
+/// assert_eq!(syllabify("gerù"), ["ge", "rù"]);
+/// ```
+pub fn syllabify(word: &str) -> Vec<String> {
+    PhonologyConfig::standard().syllabify(word)
+}
+
+// Which grapheme each syllable of a word written without accent marks starts at. Adding marks
+// over letters doesn't change how many graphemes a word has, so these split its accented form too.
+pub(crate) fn syllable_starts(plain: &str, config: &PhonologyConfig) -> Vec<usize> {
+    let mut starts = syllables::syllable_starts(plain, config)
+        .into_iter()
+        .peekable();
+    let mut grapheme_starts = Vec::new();
+    let mut chars = 0;
+    for (i, grapheme) in plain.graphemes(true).enumerate() {
+        chars += grapheme.chars().count();
+        while starts.next_if(|&start| start < chars).is_some() {
+            grapheme_starts.push(i);
+        }
+    }
+    grapheme_starts
+}
+
+pub(crate) fn split_graphemes(word: &str, starts: &[usize]) -> Vec<String> {
+    let mut syllables: Vec<String> = Vec::with_capacity(starts.len());
+    for (i, grapheme) in word.graphemes(true).enumerate() {
+        if starts.contains(&i) || syllables.is_empty() {
+            syllables.push(String::new());
+        }
+        if let Some(syllable) = syllables.last_mut() {
+            syllable.push_str(grapheme);
+        }
+    }
+    syllables
+}
+
+/// Takes a word and returns it with lithuanian accent marks for every case the engine knows,
+/// keyed by the Lithuanian case name. Cases the engine has no option for are left out.
+///
+/// If any of the cases can't be written, such as when its stress falls on a letter that can't
+/// take that stress type, this is the error for the first of them. Use
+/// [`get_all_accentuation_results`] to get the other cases anyway.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::get_all_accentuations;
+///
+/// let cases = get_all_accentuations("žodis").unwrap();
+/// assert_eq!(cases.get("Galininkas"), Some(&String::from("žõdį")));
+/// ```
+pub fn get_all_accentuations(word: &str) -> Result<HashMap<String, String>, PhonologyError> {
+    Python::with_gil(|py| get_all_accentuations_py(py, word))
+}
+
+/// Like [`get_all_accentuations`], but uses a GIL that is already held.
+pub fn get_all_accentuations_py(
+    py: Python<'_>,
+    word: &str,
+) -> Result<HashMap<String, String>, PhonologyError> {
+    accentuations_by_case(word, &get_stress_options_py(py, word)?)
+        .into_iter()
+        .map(|(case, accented)| Ok((case, accented?)))
+        .collect()
+}
+
+/// Like [`get_all_accentuations`], but a case that can't be written doesn't stop the others: each
+/// case has its own result. Only a failure to get the options at all is an error for the whole
+/// word.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::get_all_accentuation_results;
+///
+/// for (case, accented) in get_all_accentuation_results("žodis").unwrap() {
+///     match accented {
+///         Ok(accented) => println!("{case}: {accented}"),
+///         Err(e) => println!("{case}: {e}"),
+///     }
+/// }
+/// ```
+pub fn get_all_accentuation_results(
+    word: &str,
+) -> Result<HashMap<String, Result<String, PhonologyError>>, PhonologyError> {
+    Python::with_gil(|py| get_all_accentuation_results_py(py, word))
+}
+
+/// Like [`get_all_accentuation_results`], but uses a GIL that is already held.
+pub fn get_all_accentuation_results_py(
+    py: Python<'_>,
+    word: &str,
+) -> Result<HashMap<String, Result<String, PhonologyError>>, PhonologyError> {
+    Ok(
+        accentuations_by_case(word, &get_stress_options_py(py, word)?)
+            .into_iter()
+            .collect(),
+    )
+}
+
+// The first option for each case, in the order the engine gives them.
+fn accentuations_by_case(
+    word: &str,
+    options: &[StressOption],
+) -> Vec<(String, Result<String, PhonologyError>)> {
+    first_option_by_case(options)
+        .into_iter()
+        .map(|(case, option)| (case.to_string(), try_stress_with_option(word, option)))
+        .collect()
+}
+
+// The first option for each known case, in the order the engine gives them.
+pub(crate) fn first_option_by_case(options: &[StressOption]) -> Vec<(&str, &StressOption)> {
+    let mut first: Vec<(&str, &StressOption)> = Vec::new();
+    for option in options {
+        let Some(current_case) = option.grammatical_case.as_deref() else {
+            continue;
+        };
+        let known = CASE_NAMES.values().any(|&c| c == current_case)
+            || registered_case(current_case).is_some();
+        if known && !first.iter().any(|&(case, _)| case == current_case) {
+            first.push((current_case, option));
+        }
+    }
+    first
+}
+
+/// Takes a word and returns every different way it can be accentuated, in the order the engine
+/// gives its options. Options that end up written the same way, like the genitive and the
+/// nominative plural of many nouns, are only listed once. Words whose meaning depends on the
+/// stress, like `kasà` (braid) and `kãsa` (digs), come out with all of their readings. An option
+/// whose stress mark can't be placed is an error, rather than a reading without a mark.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::minimal_pair;
+///
+/// for accented in minimal_pair("kasa").unwrap() {
+///     println!("{accented}");
+/// }
+/// ```
+pub fn minimal_pair(word: &str) -> Result<Vec<String>, PhonologyError> {
+    Python::with_gil(|py| distinct_accentuations(word, &get_stress_options_py(py, word)?))
+}
+
+/// Like [`minimal_pair`], but only with the readings of the word in one case, for homographs like
+/// `kasà` and `kãsa` that are both nominatives. They're in the order the engine gives the options
+/// in, which is its ranking of them. Words that don't inflect have all of their readings in every
+/// case (see [`accentuate`]).
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::{accentuate_all_readings, Case};
+///
+/// let readings = accentuate_all_readings("kasa", Case::Nominative).unwrap();
+/// println!("{}", readings.join(" / "));
+/// ```
+pub fn accentuate_all_readings(word: &str, case: Case) -> Result<Vec<String>, PhonologyError> {
+    let readings = readings_in_case(word, get_stress_options(word)?, case)?;
+    if readings.is_empty() {
+        return Err(PhonologyError::CaseNotFound {
+            word: word.to_string(),
+            case: Some(case.lithuanian_name().to_string()),
+            number: None,
+        });
+    }
+    Ok(readings)
+}
+
+/// Like [`accentuate_all_readings`], but with every reading's stress mark on a single rendering of
+/// the word, for showing where the readings disagree at a glance: `kasa` with the readings `kasà`
+/// and `kãsa` comes out as `kãsà`.
+///
+/// When two readings stress the same letter differently, both marks go on that letter, in the
+/// order of the readings, so an `a` that is circumflex in one and acute in the other becomes
+/// `a` with a tilde and then an acute above it; a font draws them stacked. A word with a single
+/// reading comes out just as [`accentuate`] would write it.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::{overlay_all_stresses, Case};
+///
+/// assert_eq!(overlay_all_stresses("kasa", Case::Nominative).unwrap(), "kãsà");
+/// ```
+pub fn overlay_all_stresses(word: &str, case: Case) -> Result<String, PhonologyError> {
+    Ok(overlay_readings(&accentuate_all_readings(word, case)?))
+}
+
+// Every reading is the same word with a different stress mark, so the marks can be told apart by
+// the grapheme they're on.
+fn overlay_readings(readings: &[String]) -> String {
+    let [first, others @ ..] = readings else {
+        return String::new();
+    };
+    if others.is_empty() {
+        return first.clone();
+    }
+    let is_stress_mark = |c: &char| ['\u{0300}', '\u{0301}', '\u{0303}'].contains(c);
+    let decomposed: Vec<Vec<String>> = readings
+        .iter()
+        .map(|reading| {
+            graphemes(&reading.nfd().collect::<String>())
+                .map(String::from)
+                .collect()
+        })
+        .collect();
+
+    let mut overlaid = String::new();
+    for (i, grapheme) in decomposed[0].iter().enumerate() {
+        overlaid.extend(grapheme.chars().filter(|c| !is_stress_mark(c)));
+        let mut marks: Vec<char> = Vec::new();
+        for reading in &decomposed {
+            let reading_marks = reading.get(i).into_iter().flat_map(|g| g.chars());
+            for mark in reading_marks.filter(is_stress_mark) {
+                if !marks.contains(&mark) {
+                    marks.push(mark);
                 }
             }
-            if let Some(n) = number {
-                let current_number: &str = i.get("number").unwrap().extract(py)?;
-                if current_number != n {
-                    meets_criteria = false;
-                }
+        }
+        overlaid.extend(marks);
+    }
+    overlaid.nfc().collect()
+}
+
+fn readings_in_case(
+    word: &str,
+    options: Vec<StressOption>,
+    case: Case,
+) -> Result<Vec<String>, PhonologyError> {
+    let uninflected = options
+        .iter()
+        .all(|option| option.grammatical_case.is_none() && option.number.is_none());
+    let in_case: Vec<StressOption> = options
+        .into_iter()
+        .filter(|option| {
+            uninflected || option.grammatical_case.as_deref() == Some(case.lithuanian_name())
+        })
+        .collect();
+    distinct_accentuations(word, &in_case)
+}
+
+// Compared NFC normalized, since the same stress can be written precomposed or combining.
+fn distinct_accentuations(
+    word: &str,
+    options: &[StressOption],
+) -> Result<Vec<String>, PhonologyError> {
+    let mut seen = HashSet::new();
+    let mut readings = Vec::new();
+    for option in options {
+        let accented = try_stress_with_option(word, option)?;
+        if seen.insert(accented.nfc().collect::<String>()) {
+            readings.push(accented);
+        }
+    }
+    Ok(readings)
+}
+
+/// Returns every stress option the engine has for a word, in the order the engine gives them.
+///
+/// Besides the case, number and gender, each option keeps every other text field the engine
+/// decoded in [`StressOption::details`]. This is how forms the case-based functions can't select
+/// are reached: participles (active and passive, present, past and future) and other declinable
+/// verb forms are decoded by the engine like any other declinable word, so their options carry
+/// the case, number and gender of the participle. The engine doesn't label which kind of
+/// participle an option belongs to, so telling them apart is left to the caller.
+///
+/// The engine is given the word NFC normalized, and each [`StressOption::stressed_letter_index`]
+/// counts the letters of `word` as it was passed in, so it's right for decomposed input (`z`
+/// followed by a combining caron) too. With such input the index is the one of the base letter.
+///
+/// Only the options of the first part of the word are read, which for a compound the engine splits
+/// into several parts is the first half; [`stress_units`] gives all of them.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::get_stress_options;
+///
+/// for option in get_stress_options("skaitantis").unwrap() {
+///     println!("{:?} {:?}: {}", option.grammatical_case, option.gender, option.stressed_letter_index);
+/// }
+/// ```
+pub fn get_stress_options(word: &str) -> Result<Vec<StressOption>, PhonologyError> {
+    Python::with_gil(|py| get_stress_options_py(py, word))
+}
+
+/// Like [`get_stress_options`], but uses a GIL that is already held.
+pub fn get_stress_options_py(
+    py: Python<'_>,
+    word: &str,
+) -> Result<Vec<StressOption>, PhonologyError> {
+    // The engine is given the word NFC normalized, so the letters it counts are the precomposed
+    // ones; its indices are moved back onto the caller's own letters afterwards.
+    let engine_form: String = word.nfc().collect();
+    get_decoded_options(py, &engine_form)?
+        .into_iter()
+        .map(|option| {
+            let mut option = stress_option_from_decoded(py, &engine_form, option)?;
+            option.stressed_letter_index = input_index(word, option.stressed_letter_index);
+            Ok(option)
+        })
+        .collect()
+}
+
+// Turns an index into the NFC form of `word` into an index into `word` itself. A letter written
+// decomposed, like `z` with a combining caron, is one letter in the NFC form but several in
+// `word`; an index onto it becomes the index of its base letter. Letters can also differ in case
+// between the two, which doesn't change the count.
+pub(crate) fn input_index(word: &str, nfc_index: usize) -> usize {
+    let mut nfc_start = 0;
+    let mut start = 0;
+    for grapheme in word.graphemes(true) {
+        let nfc_len = grapheme.nfc().count();
+        let len = grapheme.chars().count();
+        if nfc_index < nfc_start + nfc_len {
+            return if nfc_len == len {
+                start + (nfc_index - nfc_start)
+            } else {
+                start
+            };
+        }
+        nfc_start += nfc_len;
+        start += len;
+    }
+    start + (nfc_index - nfc_start)
+}
+
+/// Returns the engine's own phonetic transcription of a word, for when an authoritative one is
+/// wanted instead of the rule-based [`transcribe`].
+///
+/// This is the `transcription` field of the word in the engine's `process` output, next to its
+/// `stress_options`. The engine writes it in its own phoneme notation rather than IPA. When the
+/// field is a list of phonemes instead of a single string, they are joined with spaces. An engine
+/// that doesn't give a transcription at all (older versions of `phonology_engine` only give
+/// stress options) is an [`PhonologyError::InvalidField`] error for the `transcription` field.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::get_transcription;
+///
+/// println!("{}", get_transcription("žodis").unwrap());
+/// ```
+pub fn get_transcription(word: &str) -> Result<String, PhonologyError> {
+    Python::with_gil(|py| get_transcription_py(py, word))
+}
+
+/// Like [`get_transcription`], but uses a GIL that is already held.
+pub fn get_transcription_py(py: Python<'_>, word: &str) -> Result<String, PhonologyError> {
+    let processed = processed_word(py, word).map_err(|e| PhonologyError::Engine {
+        word: word.to_string(),
+        message: e.to_string(),
+    })?;
+    let transcription = || -> PyResult<String> {
+        let field = processed.get_item("transcription")?;
+        match field.extract::<String>() {
+            Ok(transcription) => Ok(transcription),
+            Err(_) => Ok(field.extract::<Vec<String>>()?.join(" ")),
+        }
+    };
+    transcription().map_err(|e| PhonologyError::InvalidField {
+        word: word.to_string(),
+        field: "transcription",
+        message: e.to_string(),
+    })
+}
+
+fn find_option<'a>(
+    options: &'a [StressOption],
+    case: Option<&str>,
+    number: Option<&str>,
+) -> Option<&'a StressOption> {
+    options.iter().find(|option| {
+        case.is_none_or(|c| option.grammatical_case.as_deref() == Some(c))
+            && number.is_none_or(|n| option.number.as_deref() == Some(n))
+    })
+}
+
+// English case names, including registered ones, are turned into the Lithuanian ones the engine
+// uses.
+fn requested_case<'a>(case: Option<&'a str>, options: &AccentuationOptions) -> Option<&'a str> {
+    case.and_then(|c| match Case::from_name(c) {
+        Some(case) => Some(case.lithuanian_name()),
+        None => registered_case(c).or(options.strict_cases.then_some(c)),
+    })
+}
+
+fn find_closest_option<'a>(
+    options: &'a [StressOption],
+    case: Option<&str>,
+) -> Option<&'a StressOption> {
+    let same_case =
+        |option: &&StressOption| case.is_none_or(|c| option.grammatical_case.as_deref() == Some(c));
+    options
+        .iter()
+        .filter(same_case)
+        .find(|option| option.number.is_none())
+        .or_else(|| options.iter().find(same_case))
+}
+
+// Importing phonology_engine and constructing its engine loads the whole dictionary, which takes
+// far longer than processing a word, so both only happen the first time a word is looked up. The
+// interpreter is never finalized (pyo3's auto-initialize leaves it running until the process
+// exits), so the cached engine stays valid for the rest of the process. `cargo bench --bench
+// engine` measures a cached lookup against constructing the engine for every word.
+static ENGINE: GILOnceCell<PyObject> = GILOnceCell::new();
+
+pub(crate) fn engine(py: Python<'_>) -> PyResult<&PyAny> {
+    ENGINE
+        .get_or_try_init(py, || {
+            let phonology = PyModule::import(py, "phonology_engine")?;
+            Ok::<_, PyErr>(phonology.getattr("PhonologyEngine")?.call0()?.into())
+        })
+        .map(|engine| engine.as_ref(py))
+}
+
+// The engine's output for the first word of `word`, which holds the stress options, the
+// transcription and everything else the engine knows about it.
+fn processed_word<'py>(py: Python<'py>, word: &str) -> PyResult<&'py PyAny> {
+    let unit = engine(py)?
+        .getattr("process")?
+        .call((word,), None)?
+        .getattr("__next__")?
+        .call0()?;
+    first_word(unit)
+}
+
+// The steps the engine's output is taken apart by. Each has a key, which is looked up when the
+// output is a dict, and an index, which is then taken of the result (or of the output itself,
+// for the nested lists the engine returns now). `None` for steps that only ever had a key.
+const FIRST_WORD_PATH: [(&str, Option<usize>); 2] = [("groups", Some(0)), ("words", Some(0))];
+const DECODED_OPTIONS_PATH: [(&str, Option<usize>); 2] =
+    [("stress_options", None), ("decoded_options", None)];
+
+// The first word of one unit of the engine's output: `unit[0][0]`, or `unit["groups"][0]["words"]
+// [0]` with any mix of dicts and lists, so that a version of the engine that changes the layout
+// still works.
+fn first_word(unit: &PyAny) -> PyResult<&PyAny> {
+    follow_path(unit, "process(...)", &FIRST_WORD_PATH)
+}
+
+// Follows `path` from `value`, which is described as `described` in errors, and says exactly which
+// step failed if one does.
+fn follow_path<'py>(
+    value: &'py PyAny,
+    described: &str,
+    path: &[(&str, Option<usize>)],
+) -> PyResult<&'py PyAny> {
+    let mut value = value;
+    let mut described = described.to_string();
+    let missing = |described: &str, what: String, value: &PyAny| {
+        let type_name = value.get_type().name().unwrap_or("unknown type");
+        pyo3::exceptions::PyLookupError::new_err(format!(
+            "engine output {described} has no {what} ({type_name})"
+        ))
+    };
+    for &(key, index) in path {
+        if let Ok(mapping) = value.downcast::<pyo3::types::PyMapping>() {
+            value = mapping
+                .get_item(key)
+                .map_err(|_| missing(&described, format!("'{key}' key"), value))?;
+            described.push_str(&format!("[{key:?}]"));
+        } else if index.is_none() {
+            return Err(missing(&described, format!("'{key}' key"), value));
+        }
+        if let Some(index) = index {
+            value = value
+                .get_item(index)
+                .map_err(|_| missing(&described, format!("item {index}"), value))?;
+            described.push_str(&format!("[{index}]"));
+        }
+    }
+    Ok(value)
+}
+
+fn get_decoded_options(
+    py: Python,
+    word: &str,
+) -> Result<Vec<HashMap<String, PyObject>>, PhonologyError> {
+    let decoded_options = || -> PyResult<_> {
+        let word = processed_word(py, word)?;
+        follow_path(word, "for the first word", &DECODED_OPTIONS_PATH)?.extract()
+    };
+    decoded_options().map_err(|e| PhonologyError::Engine {
+        word: word.to_string(),
+        message: e.to_string(),
+    })
+}
+
+fn extract_field<'py, T: FromPyObject<'py>>(
+    py: Python<'py>,
+    word: &str,
+    option: &'py HashMap<String, PyObject>,
+    field: &'static str,
+) -> Result<T, PhonologyError> {
+    let invalid = |message: String| PhonologyError::InvalidField {
+        word: word.to_string(),
+        field,
+        message,
+    };
+    option
+        .get(field)
+        .ok_or_else(|| invalid(String::from("the field is missing")))?
+        .extract(py)
+        .map_err(|e| invalid(e.to_string()))
+}
+
+pub(crate) fn stress_option_from_decoded(
+    py: Python,
+    word: &str,
+    option: HashMap<String, PyObject>,
+) -> Result<StressOption, PhonologyError> {
+    let stress_type = extract_field(py, word, &option, "stress_type")?;
+    let stressed_letter_index = extract_field(py, word, &option, "stressed_letter_index")?;
+    let stressed_letter_index = resolve_stressed_letter_index(word, stressed_letter_index)?;
+
+    let mut details: HashMap<String, String> = option
+        .iter()
+        .filter(|(k, _)| !["stress_type", "stressed_letter_index"].contains(&k.as_str()))
+        .filter_map(|(k, v)| Some((k.clone(), detail_text(py, v)?)))
+        .collect();
+    Ok(StressOption {
+        stress_type,
+        stressed_letter_index,
+        grammatical_case: details.remove("grammatical_case"),
+        number: details.remove("number"),
+        gender: details.remove("gender"),
+        details,
+    })
+}
+
+// Some words come back from the engine with a stressed letter index that only makes sense counted
+// from the end of the word, like a negative Python index. Those are read that way as long as they
+// land on a letter that can be stressed, with a warning since it means the engine is using a
+// different convention than expected.
+fn resolve_stressed_letter_index(word: &str, index: i64) -> Result<usize, PhonologyError> {
+    let len = word.chars().count() as i64;
+    if (0..len).contains(&index) {
+        return Ok(index as usize);
+    }
+    let from_end = len.saturating_add(index);
+    let stressable = (0..len).contains(&from_end) && {
+        let from_end = from_end as usize;
+        syllables::nuclei(word).iter().any(|n| n.contains(from_end))
+            || syllables::past_softening_i(word, from_end).is_some()
+    };
+    if !stressable {
+        return Err(PhonologyError::InvalidStressIndex {
+            word: word.to_string(),
+            index,
+        });
+    }
+    log::warn!(
+        "stressed letter index {index} for word '{word}' is out of range, reading it as {from_end} counted from the end"
+    );
+    Ok(from_end as usize)
+}
+
+pub(crate) fn detail_text(py: Python<'_>, value: &PyObject) -> Option<String> {
+    if let Ok(text) = value.extract::<String>(py) {
+        Some(text)
+    } else if let Ok(flag) = value.extract::<bool>(py) {
+        Some(flag.to_string())
+    } else if let Ok(number) = value.extract::<i64>(py) {
+        Some(number.to_string())
+    } else {
+        value
+            .extract::<f64>(py)
+            .ok()
+            .map(|number| number.to_string())
+    }
+}
+
+// `word` with the stress mark of `option`, or an error if the mark can't be placed.
+pub(crate) fn try_stress_with_option(
+    word: &str,
+    option: &StressOption,
+) -> Result<String, PhonologyError> {
+    create_stresed_word(word, option.stress_type, effective_index(word, option))
+}
+
+// The engine's index isn't always where the mark should go:
+//
+// - For nouns ending in -is, -ys and -ius it sometimes puts the stress on the i that softens the
+//   consonant before an ending like -io or -iu, such as the genitive of kelys. That i isn't a
+//   syllable nucleus, so the stress belongs to the vowel after it.
+// - A sonorant can only carry stress as the second half of a mixed diphthong like the il in
+//   pilnas. Stress on any other sonorant belongs to the vowel before it.
+//
+// Either way the mark is only moved if the new letter can be written with the same stress type.
+pub(crate) fn effective_index(word: &str, option: &StressOption) -> usize {
+    let index = option.stressed_letter_index;
+    syllables::past_softening_i(word, index)
+        .or_else(|| syllables::off_lone_sonorant(word, index))
+        .filter(|&moved| {
+            word.chars().nth(moved).is_some_and(|c| {
+                let lower = c.to_lowercase().next().unwrap_or(c);
+                stress_map(option.stress_type).is_some_and(|map| map.contains_key(&lower))
+            })
+        })
+        .unwrap_or(index)
+}
+
+// Which grapheme of the word the mark for an option goes on. Marks over letters don't change how
+// many graphemes a word has, so this is the same grapheme in the accented word. An index past the
+// end of the word is past its last grapheme too.
+pub(crate) fn stressed_grapheme(word: &str, option: &StressOption) -> usize {
+    let index = effective_index(word, option);
+    let mut chars = 0;
+    word.graphemes(true)
+        .take_while(|grapheme| {
+            chars += grapheme.chars().count();
+            chars <= index
+        })
+        .count()
+}
+
+/// Splits an accented word into the letters a reader sees, keeping combining stress marks
+/// together with the letter they sit on. Use this instead of `.chars()` whenever moving over,
+/// cutting or highlighting accented text.
+///
+/// # Examples
+///
+/// ```
+/// use lithuanian_phonology::graphemes;
+///
+/// assert_eq!(graphemes("ą̃žuolas").count(), 7);
+/// assert_eq!(graphemes("ą̃žuolas").next(), Some("ą̃"));
+/// ```
+pub fn graphemes(accented: &str) -> impl Iterator<Item = &str> {
+    accented.graphemes(true)
+}
+
+/// Returns how many columns an accented word takes up when printed, not counting the combining
+/// marks that are drawn on top of the previous letter.
+///
+/// # Examples
+///
+/// ```
+/// use lithuanian_phonology::display_width;
+///
+/// assert_eq!(display_width("gerà"), 4);
+/// assert_eq!(display_width("ą̃žuolas"), 7);
+/// ```
+pub fn display_width(accented: &str) -> usize {
+    graphemes(accented).count()
+}
+
+/// Shortens accented text to at most `max` letters as a reader sees them (see [`graphemes`]),
+/// ending it with `…` if anything was cut, for fitting words into a narrow space. A stress mark is
+/// never cut off the letter it's on. The `…` counts as one of the `max` letters, and text that
+/// already fits comes back unchanged.
+///
+/// # Examples
+///
+/// ```
+/// use lithuanian_phonology::truncate_graphemes;
+///
+/// assert_eq!(truncate_graphemes("ą̃žuolas", 4), "ą̃žu…");
+/// assert_eq!(truncate_graphemes("gerà", 4), "gerà");
+/// ```
+pub fn truncate_graphemes(accented: &str, max: usize) -> String {
+    if graphemes(accented).nth(max).is_none() {
+        return accented.to_string();
+    }
+    let Some(kept) = max.checked_sub(1) else {
+        return String::new();
+    };
+    let mut truncated: String = graphemes(accented).take(kept).collect();
+    truncated.push('…');
+    truncated
+}
+
+/// Takes the stress marks off a word, undoing the crate's accentuation: the combining grave, acute
+/// and tilde, and the spacing marks that [`MarkPlacement::Preceding`] writes. Other marks, like
+/// the ogonek of `ą` and the dot of `ė`, are part of the letter and stay. The word comes back in
+/// NFC whether or not it was.
+///
+/// # Examples
+///
+/// ```
+/// use lithuanian_phonology::strip_accentuation;
+///
+/// assert_eq!(strip_accentuation("ą̃žuolas"), "ąžuolas");
+/// assert_eq!(strip_accentuation("Žõdį"), "Žodį");
+/// assert_eq!(strip_accentuation("gerà"), "gera");
+/// ```
+pub fn strip_accentuation(word: &str) -> String {
+    word.nfd()
+        .filter(|c| {
+            ![
+                '\u{0300}', '\u{0301}', '\u{0303}', '\u{02CB}', '\u{02CA}', '\u{02DC}',
+            ]
+            .contains(c)
+        })
+        .nfc()
+        .collect()
+}
+
+// The Lithuanian alphabet in order, with the q, w and x of loanwords where they are in Latin.
+const ALPHABET: [char; 35] = [
+    'a', 'ą', 'b', 'c', 'č', 'd', 'e', 'ę', 'ė', 'f', 'g', 'h', 'i', 'į', 'y', 'j', 'k', 'l', 'm',
+    'n', 'o', 'p', 'q', 'r', 's', 'š', 't', 'u', 'ų', 'ū', 'v', 'w', 'x', 'z', 'ž',
+];
+
+/// A key for sorting words a tergo, by their endings, the way reverse dictionaries do: words that
+/// end the same way, like the forms of nouns in one declension, end up next to each other.
+///
+/// The key is the word without its stress marks (see [`strip_accentuation`]) and in lowercase,
+/// read from its last letter to its first, with each letter replaced so that comparing keys
+/// follows the Lithuanian alphabet (`y` right after `į`, `ž` last) instead of Unicode's order.
+/// It's only meant for comparing, not for reading. Characters that aren't Lithuanian letters are
+/// kept as they are and sort around them by their code points, hyphens and digits before every
+/// letter and other scripts after.
+///
+/// # Examples
+///
+/// ```
+/// use lithuanian_phonology::a_tergo_key;
+///
+/// let mut words = ["ačiū", "žõdis", "výras", "kelìs", "kãtinas"];
+/// words.sort_by_key(|word| a_tergo_key(word));
+/// assert_eq!(words, ["kãtinas", "výras", "žõdis", "kelìs", "ačiū"]);
+/// ```
+pub fn a_tergo_key(word: &str) -> String {
+    strip_accentuation(word)
+        .to_lowercase()
+        .chars()
+        .rev()
+        .map(|c| match ALPHABET.iter().position(|&letter| letter == c) {
+            Some(position) => char::from(b'A' + position as u8),
+            None => c,
+        })
+        .collect()
+}
+
+/// Uppercases an accented word, such as for a headword, keeping its stress marks on the right
+/// letters. [`str::to_uppercase`] already keeps combining marks with their letter, but doesn't
+/// know the one Lithuanian casing rule: the dot that stays on a lowercase `i` or `j` under a
+/// stress mark, like in `i̇̃`, goes away in uppercase.
+///
+/// # Examples
+///
+/// ```
+/// use lithuanian_phonology::to_uppercase_lithuanian;
+///
+/// assert_eq!(to_uppercase_lithuanian("gerà"), "GERÀ");
+/// assert_eq!(to_uppercase_lithuanian("ą̃žuolas"), "Ą̃ŽUOLAS");
+/// assert_eq!(to_uppercase_lithuanian("ki\u{0307}\u{0303}lti"), "KĨLTI");
+/// ```
+pub fn to_uppercase_lithuanian(accented: &str) -> String {
+    let mut uppercase = String::with_capacity(accented.len());
+    for grapheme in graphemes(accented) {
+        let soft_dotted = grapheme
+            .chars()
+            .next()
+            .is_some_and(|c| ['i', 'į', 'j'].contains(&c));
+        let kept = grapheme
+            .chars()
+            .filter(|&c| !(soft_dotted && c == '\u{0307}'));
+        let upper: String = kept.flat_map(char::to_uppercase).collect();
+        uppercase.extend(upper.nfc());
+    }
+    uppercase
+}
+
+/// Puts every run of combining marks in `text` in the Unicode canonical order, sorted by combining
+/// class with marks of the same class kept as they are, without composing or decomposing anything.
+/// Marks below a letter, like the ogonek, come before the marks above it, so a letter like `ų̃` is
+/// always `ų` followed by the tilde however the marks were typed. Some shaping engines draw
+/// stacked marks wrongly in any other order.
+///
+/// The accented words the crate returns are already in this order; this is for text put together
+/// from other sources.
+///
+/// # Examples
+///
+/// ```
+/// use lithuanian_phonology::reorder_combining_marks;
+///
+/// // a tilde typed before the ogonek
+/// assert_eq!(reorder_combining_marks("u\u{0303}\u{0328}"), "u\u{0328}\u{0303}");
+/// assert_eq!(reorder_combining_marks("ų̃"), "ų̃");
+/// ```
+pub fn reorder_combining_marks(text: &str) -> String {
+    let mut reordered = String::with_capacity(text.len());
+    let mut marks = Vec::new();
+    for c in text.chars() {
+        if canonical_combining_class(c) == 0 {
+            marks.sort_by_key(|&mark| canonical_combining_class(mark));
+            reordered.extend(marks.drain(..));
+            reordered.push(c);
+        } else {
+            marks.push(c);
+        }
+    }
+    marks.sort_by_key(|&mark| canonical_combining_class(mark));
+    reordered.extend(marks);
+    reordered
+}
+
+// An index past the end of the word, a letter that can't carry the stress type, or a stress type
+// the crate doesn't know is an error rather than a panic or an unstressed word, whatever the input.
+pub(crate) fn create_stresed_word(
+    word: &str,
+    stress_type: u8,
+    stressed_letter_index: usize,
+) -> Result<String, PhonologyError> {
+    place_stress(
+        word,
+        stress_type,
+        stressed_letter_index,
+        MarkPlacement::Over,
+    )
+}
+
+pub(crate) fn place_stress(
+    word: &str,
+    stress_type: u8,
+    stressed_letter_index: usize,
+    placement: MarkPlacement,
+) -> Result<String, PhonologyError> {
+    if word.chars().nth(stressed_letter_index).is_none() {
+        return Err(out_of_bounds(word, stressed_letter_index));
+    }
+    let mut stressed = String::new();
+    let mut chars = word.chars().enumerate().peekable();
+    while let Some((i, c)) = chars.next() {
+        if i != stressed_letter_index {
+            stressed.push(c);
+            continue;
+        }
+        // A decomposed letter like `a` with a combining ogonek is stressed as the precomposed
+        // `ą`, so that the stress mark comes after the ogonek. Marks that can't be composed with
+        // the letter are kept between it and the stress mark.
+        let mut marks = String::new();
+        while let Some((_, mark)) = chars.next_if(|&(_, next)| is_combining_mark(next)) {
+            marks.push(mark);
+        }
+        let composed: Vec<char> = format!("{c}{marks}").nfc().collect();
+        let c = match composed[..] {
+            [single] => {
+                marks.clear();
+                single
+            }
+            _ => c,
+        };
+        let unsupported = || PhonologyError::UnsupportedStress {
+            word: word.to_string(),
+            letter: c,
+            stress_type,
+        };
+        let marked = match placement {
+            MarkPlacement::Over if c.is_uppercase() => {
+                let lower = c.to_lowercase().next().unwrap_or(c);
+                make_stressed(lower, stress_type)
+                    .ok_or_else(unsupported)?
+                    .to_uppercase()
             }
-            if meets_criteria {
-                let stress_type: u8 = i.get("stress_type").unwrap().extract(py)?;
-                let stressed_letter_index: usize =
-                    i.get("stressed_letter_index").unwrap().extract(py)?;
-                return Ok(create_stresed_word(
-                    word,
-                    stress_type,
-                    stressed_letter_index,
-                ));
+            MarkPlacement::Over => make_stressed(c, stress_type)
+                .ok_or_else(unsupported)?
+                .to_string(),
+            MarkPlacement::Preceding => {
+                let mark = MarkPlacement::preceding_mark(stress_type).ok_or_else(unsupported)?;
+                stressed.push_str(mark);
+                stressed.push(c);
+                stressed.push_str(&marks);
+                continue;
             }
+        };
+        if marks.is_empty() {
+            stressed.push_str(&marked);
+        } else {
+            // The letter's own marks go between it and the stress mark, which is always on top,
+            // and are then sorted into canonical order with the stress mark.
+            let mut letter = marked.nfd();
+            stressed.extend(letter.next());
+            let mut rest = marks;
+            rest.extend(letter);
+            stressed.push_str(&reorder_combining_marks(&rest));
         }
+    }
+    Ok(stressed)
+}
 
-        Err("Unable to find correct case".into())
-    })
+pub(crate) fn out_of_bounds(word: &str, index: usize) -> PhonologyError {
+    PhonologyError::StressIndexOutOfBounds {
+        word: word.to_string(),
+        index,
+        word_len: word.chars().count(),
+    }
 }
 
-fn create_stresed_word(word: &str, stress_type: u8, stressed_letter_index: usize) -> String {
-    let mut stressed = String::new();
-    for (i, c) in word.chars().enumerate() {
-        if i == stressed_letter_index {
-            stressed.push_str(make_stressed(c, stress_type));
-        } else {
-            stressed.push(c);
+// Writes every long vowel with a combining tilde, like `ū̃`, as the vowel and its base letter with a
+// precomposed tilde, like `ūũ`.
+fn double_long_circumflexes(accented: &str) -> String {
+    let mut doubled = String::with_capacity(accented.len() + 2);
+    let mut chars = accented.chars().peekable();
+    while let Some(c) = chars.next() {
+        doubled.push(c);
+        // a long vowel with a mark of its own decomposes into its base letter and that mark
+        let decomposed: Vec<char> = c.nfd().collect();
+        if syllables::vowel_length(c) != Some(syllables::VowelLength::Long) || decomposed.len() < 2
+        {
+            continue;
+        }
+        if chars.next_if_eq(&'\u{0303}').is_some() {
+            doubled.extend([decomposed[0], '\u{0303}'].into_iter().nfc());
+        }
+    }
+    doubled
+}
+
+// Writes every precomposed letter with a grave, like `à`, as the letter and U+0300.
+fn decompose_graves(accented: &str) -> String {
+    let mut decomposed = String::with_capacity(accented.len() + 1);
+    for c in accented.chars() {
+        match c.nfd().collect::<Vec<_>>()[..] {
+            [letter, '\u{0300}'] => decomposed.extend([letter, '\u{0300}']),
+            _ => decomposed.push(c),
+        }
+    }
+    decomposed
+}
+
+/// Where the stress mark goes. See [`AccentuationOptions::mark_placement`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MarkPlacement {
+    /// Combined with the stressed letter, like `žõdį`.
+    #[default]
+    Over,
+    /// A separate mark right before the stressed letter, like `ž˜odį`, which some dictionaries
+    /// use and which is easier to see when fonts draw combining marks badly.
+    Preceding,
+}
+
+impl MarkPlacement {
+    /// The separate mark [`MarkPlacement::Preceding`] puts before the stressed letter: `ˋ`
+    /// (U+02CB) for a short stress, `ˊ` (U+02CA) for an acute and `˜` (U+02DC) for a circumflex.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lithuanian_phonology::MarkPlacement;
+    ///
+    /// assert_eq!(MarkPlacement::preceding_mark(2), Some("˜"));
+    /// assert_eq!(MarkPlacement::preceding_mark(3), None);
+    /// ```
+    pub fn preceding_mark(stress_type: u8) -> Option<&'static str> {
+        match stress_type {
+            0 => Some("\u{02CB}"),
+            1 => Some("\u{02CA}"),
+            2 => Some("\u{02DC}"),
+            _ => None,
         }
     }
-    stressed
 }
 
-static STRESS_TYPE_2: phf::Map<char, &str> = phf_map! {
+/// Letters that can take stress type 2 (circumflex, the tilde) and how they look with it.
+pub static STRESS_TYPE_2: phf::Map<char, &str> = phf_map! {
+    'a' => "ã",
     'ą' => "ą̃",
     'e' => "ẽ",
     'ė' => "ė̃",
     'ę' => "ę̃",
+    'i' => "ĩ",
     'į' => "į̃",
     'l' => "l̃",
     'm' => "m̃",
+    'n' => "ñ",
     'o' => "õ",
     'r' => "r̃",
+    'u' => "ũ",
     'ų' => "ų̃",
     'ū' => "ū̃",
     'y' => "ỹ",
 };
 
-static STRESS_TYPE_0: phf::Map<char, &str> = phf_map! {
+/// Letters that can take stress type 0 (short stress, the grave) and how they look with it.
+pub static STRESS_TYPE_0: phf::Map<char, &str> = phf_map! {
     'a' => "à",
+    'e' => "è",
     'i' => "ì",
+    'o' => "ò",
     'u' => "ù",
 };
 
-static STRESS_TYPE_1: phf::Map<char, &str> = phf_map! {
+/// Letters that can take stress type 1 (acute) and how they look with it.
+pub static STRESS_TYPE_1: phf::Map<char, &str> = phf_map! {
     'ū' => "ū́",
-    'e' => "ę́",
+    'a' => "á",
+    'e' => "é",
+    'i' => "í",
+    'u' => "ú",
+    'ę' => "ę́",
     'ė' => "ė́",
     'į' => "į́",
     'ą' => "ą́",
     'ų' => "ų́",
+    'o' => "ó",
+    'y' => "ý",
 };
 
-static CASE_NAMES: phf::Map<&str, &str> = phf_map! {
+static CASE_NAMES: phf::OrderedMap<&str, &str> = phf_ordered_map! {
     "nominative" => "Vardininkas",
     "genitive" => "Kilmininkas",
     "dative" => "Naudininkas",
@@ -117,8 +1889,71 @@ static CASE_NAMES: phf::Map<&str, &str> = phf_map! {
     "vocative" => "Šauksmininkas",
 };
 
+// Cases added with `register_case`, as (lowercase english name, Lithuanian name). The Lithuanian
+// names are leaked so they can be handed out like the static ones.
+static EXTRA_CASES: RwLock<Vec<(String, &'static str)>> = RwLock::new(Vec::new());
+
+// Every Lithuanian name `register_case` has leaked, so that registering a name again reuses it
+// and a program that keeps registering the same cases doesn't leak more and more.
+static LEAKED_CASE_NAMES: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+
+/// Teaches the crate about a case beyond the standard seven that an engine build produces, such as
+/// the illative or allative of older grammars and some dialects. `lithuanian` is the name the
+/// engine gives the case in its options.
+///
+/// Afterwards [`get_case_name`] translates `english` too, and the functions that take a case by
+/// name, like [`get_accentuation`] and [`accentuate`], accept either name for it. It also stops
+/// the case from being skipped by [`get_all_accentuations`]. There is no [`Case`] variant for it,
+/// so the functions that take a [`Case`] can't ask for it.
+///
+/// Registering an english name again replaces its Lithuanian name. The seven standard cases can't
+/// be replaced: their names are always looked up first.
+///
+/// # Examples
+///
+/// ```
+/// use lithuanian_phonology::{get_case_name, register_case};
+///
+/// assert_eq!(get_case_name("Illative"), "UNKNOWN");
+/// register_case("illative", "Kryptininkas");
+/// assert_eq!(get_case_name("Illative"), "Kryptininkas");
+/// ```
+pub fn register_case(english: &str, lithuanian: &str) {
+    let english = english.to_lowercase();
+    let lithuanian = leaked_case_name(lithuanian);
+    let mut cases = EXTRA_CASES.write().unwrap_or_else(PoisonError::into_inner);
+    match cases.iter_mut().find(|(name, _)| *name == english) {
+        Some(case) => case.1 = lithuanian,
+        None => cases.push((english, lithuanian)),
+    }
+}
+
+fn leaked_case_name(name: &str) -> &'static str {
+    let mut leaked = LEAKED_CASE_NAMES
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    if let Some(&existing) = leaked.iter().find(|&&leaked| leaked == name) {
+        return existing;
+    }
+    let name: &'static str = Box::leak(name.to_string().into_boxed_str());
+    leaked.push(name);
+    name
+}
+
+// The Lithuanian name of a registered case, looked up by either of its names.
+fn registered_case(name: &str) -> Option<&'static str> {
+    let name = name.to_lowercase();
+    EXTRA_CASES
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .find(|(english, lithuanian)| *english == name || lithuanian.to_lowercase() == name)
+        .map(|(_, lithuanian)| *lithuanian)
+}
+
 /// Utility function that takes an english name of a case, and converts it into Lithuanian.
-/// Useful when paired with get_accentuation()
+/// Useful when paired with get_accentuation(). Cases added with [`register_case`] are looked up
+/// after the standard seven.
 ///
 /// # Examples
 ///
@@ -131,16 +1966,1287 @@ static CASE_NAMES: phf::Map<&str, &str> = phf_map! {
 pub fn get_case_name(case: &str) -> &str {
     match CASE_NAMES.get(&case.to_lowercase()) {
         Some(val) => val,
-        None => "UNKNOWN",
+        None => registered_case(case).unwrap_or("UNKNOWN"),
+    }
+}
+
+/// One of the seven Lithuanian grammatical cases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Case {
+    Nominative,
+    Genitive,
+    Dative,
+    Accusative,
+    Instrumental,
+    Locative,
+    Vocative,
+}
+
+impl Case {
+    /// Every case, in the order they're listed in declension tables.
+    pub const ALL: [Case; 7] = [
+        Case::Nominative,
+        Case::Genitive,
+        Case::Dative,
+        Case::Accusative,
+        Case::Instrumental,
+        Case::Locative,
+        Case::Vocative,
+    ];
+
+    /// The lowercase english name of the case, such as `nominative`.
+    pub fn english_name(self) -> &'static str {
+        match self {
+            Case::Nominative => "nominative",
+            Case::Genitive => "genitive",
+            Case::Dative => "dative",
+            Case::Accusative => "accusative",
+            Case::Instrumental => "instrumental",
+            Case::Locative => "locative",
+            Case::Vocative => "vocative",
+        }
+    }
+
+    /// The Lithuanian name of the case as the engine uses it, such as `Vardininkas`.
+    pub fn lithuanian_name(self) -> &'static str {
+        get_case_name(self.english_name())
+    }
+
+    /// Looks up a case by its english or Lithuanian name, ignoring capitalization.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lithuanian_phonology::Case;
+    ///
+    /// assert_eq!(Case::from_name("Accusative"), Some(Case::Accusative));
+    /// assert_eq!(Case::from_name("galininkas"), Some(Case::Accusative));
+    /// assert_eq!(Case::from_name("ablative"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Case> {
+        let name = name.to_lowercase();
+        Case::ALL.into_iter().find(|case| {
+            case.english_name() == name || case.lithuanian_name().to_lowercase() == name
+        })
+    }
+}
+
+/// Every letter that can carry each stress type, whether or not the stress maps have it:
+///
+/// - short stress goes on the short vowels `a`, `e`, `i`, `u`, and `o` in loanwords
+/// - acutes go on long vowels, and on the first letter of a diphthong (`áu`, `íe`, `úo`)
+/// - circumflexes go on long vowels, on the second letter of a diphthong (`aũ`, `iẽ`, `uõ`), and
+///   on the sonorant of a mixed diphthong (`il̃`, `añ`)
+fn stressable(stress_type: u8) -> Option<Vec<char>> {
+    use syllables::{is_diphthong_sonorant, vowel_length, VowelLength};
+    let can_carry = |c: char| match stress_type {
+        0 => vowel_length(c).is_some_and(|length| length != VowelLength::Long) || c == 'o',
+        1 => vowel_length(c).is_some(),
+        2 => vowel_length(c).is_some() || is_diphthong_sonorant(c),
+        _ => false,
+    };
+    (stress_type <= 2).then(|| ALPHABET.into_iter().filter(|&c| can_carry(c)).collect())
+}
+
+/// Returns the letters that can carry a stress type in Lithuanian but have no entry in its stress
+/// map, so that gaps in the maps are easy to find. Empty for stress types the engine doesn't use.
+///
+/// # Examples
+///
+/// ```
+/// use lithuanian_phonology::uncovered_letters;
+///
+/// assert!(uncovered_letters(2).is_empty());
+/// ```
+pub fn uncovered_letters(stress_type: u8) -> Vec<char> {
+    let (Some(letters), Some(map)) = (stressable(stress_type), stress_map(stress_type)) else {
+        return Vec::new();
+    };
+    letters
+        .into_iter()
+        .filter(|letter| !map.contains_key(letter))
+        .collect()
+}
+
+/// Returns the map of letters to their stressed forms for one of the engine's stress types, or
+/// `None` if the stress type isn't one the engine uses.
+///
+/// # Examples
+///
+/// ```
+/// use lithuanian_phonology::stress_map;
+///
+/// assert_eq!(stress_map(0).unwrap().get(&'a'), Some(&"à"));
+/// assert!(stress_map(2).unwrap().contains_key(&'ū'));
+/// assert!(stress_map(3).is_none());
+/// ```
+pub fn stress_map(stress_type: u8) -> Option<&'static phf::Map<char, &'static str>> {
+    match stress_type {
+        0 => Some(&STRESS_TYPE_0),
+        1 => Some(&STRESS_TYPE_1),
+        2 => Some(&STRESS_TYPE_2),
+        _ => None,
+    }
+}
+
+/// The kind of stress a syllable has, as it's taught: short stress, or one of the two
+/// priegaidės (acute or circumflex) on long syllables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NormalizedStress {
+    /// Stress on a short vowel, written with a grave.
+    Short,
+    /// Falling tone on a long vowel or diphthong, written with an acute.
+    Acute,
+    /// Rising tone on a long vowel or diphthong, written with a tilde.
+    Circumflex,
+}
+
+impl NormalizedStress {
+    /// The lowercase english name of the stress, such as `circumflex`.
+    pub fn english_name(self) -> &'static str {
+        match self {
+            NormalizedStress::Short => "short",
+            NormalizedStress::Acute => "acute",
+            NormalizedStress::Circumflex => "circumflex",
+        }
+    }
+}
+
+/// Reconciles the engine's stress type with the letter that carries it, when that letter is a
+/// syllable nucleus on its own. The engine can report an acute or circumflex on `i` or `u`, but
+/// as single vowels those are always short and can't have a priegaidė, so those become
+/// [`NormalizedStress::Short`]:
+///
+/// | stress type | letter                                 | result       |
+/// |-------------|----------------------------------------|--------------|
+/// | 0           | any                                    | `Short`      |
+/// | 1           | `i`, `u`                               | `Short`      |
+/// | 1           | any other vowel or sonorant            | `Acute`      |
+/// | 2           | `i`, `u`                               | `Short`      |
+/// | 2           | any other vowel, or `l`, `m`, `n`, `r` | `Circumflex` |
+///
+/// An `i` or `u` that is part of a diphthong or a mixed diphthong is part of a long nucleus, and
+/// keeps its priegaidė: `laũkas`, `geraĩ` and `díena` are circumflex, circumflex and acute. A
+/// single letter can't say which it is, so the functions that take a word, like
+/// [`crate::analyze`] and [`crate::to_ssml`], look at the nucleus the letter is in instead.
+///
+/// Returns `None` for stress types the engine doesn't use.
+///
+/// # Examples
+///
+/// ```
+/// use lithuanian_phonology::{normalize_stress_type, NormalizedStress};
+///
+/// assert_eq!(normalize_stress_type(0, 'a'), Some(NormalizedStress::Short));
+/// assert_eq!(normalize_stress_type(1, 'ū'), Some(NormalizedStress::Acute));
+/// assert_eq!(normalize_stress_type(2, 'l'), Some(NormalizedStress::Circumflex));
+/// assert_eq!(normalize_stress_type(1, 'i'), Some(NormalizedStress::Short));
+/// assert_eq!(normalize_stress_type(3, 'a'), None);
+/// ```
+pub fn normalize_stress_type(stress_type: u8, nucleus: char) -> Option<NormalizedStress> {
+    let nucleus = nucleus.to_lowercase().next().unwrap_or(nucleus);
+    match stress_type {
+        0 => Some(NormalizedStress::Short),
+        1 | 2 if syllables::vowel_length(nucleus) == Some(syllables::VowelLength::Short) => {
+            Some(NormalizedStress::Short)
+        }
+        1 => Some(NormalizedStress::Acute),
+        2 => Some(NormalizedStress::Circumflex),
+        _ => None,
+    }
+}
+
+// Like `normalize_stress_type`, for the letter at `index` of a word: stress on a letter of a
+// diphthong or a mixed diphthong is never collapsed to short stress, since the nucleus is long.
+pub(crate) fn normalize_stress_at(
+    word: &str,
+    index: usize,
+    stress_type: u8,
+) -> Option<NormalizedStress> {
+    let letter = word.chars().nth(index)?;
+    let in_long_nucleus = syllables::nuclei(word)
+        .iter()
+        .any(|n| n.kind != syllables::NucleusKind::Vowel && n.contains(index));
+    match stress_type {
+        1 if in_long_nucleus => Some(NormalizedStress::Acute),
+        2 if in_long_nucleus => Some(NormalizedStress::Circumflex),
+        _ => normalize_stress_type(stress_type, letter),
+    }
+}
+
+/// The combining mark for a stress type, without any letter under it.
+///
+/// # Examples
+///
+/// ```
+/// use lithuanian_phonology::stress_mark;
+///
+/// assert_eq!(stress_mark(2), Some("\u{0303}"));
+/// assert_eq!(stress_mark(3), None);
+/// ```
+pub fn stress_mark(stress_type: u8) -> Option<&'static str> {
+    match stress_type {
+        0 => Some("\u{0300}"),
+        1 => Some("\u{0301}"),
+        2 => Some("\u{0303}"),
+        _ => None,
     }
 }
 
-fn make_stressed<'a>(c: char, stress_type: u8) -> &'a str {
-    let map = match stress_type {
-        0 => &STRESS_TYPE_0,
-        1 => &STRESS_TYPE_1,
-        2 => &STRESS_TYPE_2,
-        _ => unreachable!(),
+fn make_stressed(c: char, stress_type: u8) -> Option<&'static str> {
+    stress_map(stress_type)?.get(&c).copied()
+}
+
+/// The codepoints a letter is written with when it carries a stress type, exactly as the stress
+/// maps render it, for telling whether a font problem comes from a precomposed letter or a
+/// combining mark. Uppercase letters are rendered like their lowercase forms and then uppercased,
+/// as they are in words. Returns `None` for letters that can't take the stress type.
+///
+/// # Examples
+///
+/// ```
+/// use lithuanian_phonology::stress_codepoints;
+///
+/// // precomposed
+/// assert_eq!(stress_codepoints(2, 'a'), Some(vec![0x00E3]));
+/// assert_eq!(stress_codepoints(2, 'A'), Some(vec![0x00C3]));
+/// // a base letter and a combining tilde
+/// assert_eq!(stress_codepoints(2, 'l'), Some(vec![0x006C, 0x0303]));
+/// assert_eq!(stress_codepoints(0, 'y'), None);
+/// ```
+pub fn stress_codepoints(stress_type: u8, c: char) -> Option<Vec<u32>> {
+    let rendered = if c.is_uppercase() {
+        let lower = c.to_lowercase().next().unwrap_or(c);
+        make_stressed(lower, stress_type)?.to_uppercase()
+    } else {
+        make_stressed(c, stress_type)?.to_string()
     };
-    map.get(&c).unwrap()
+    Some(rendered.chars().map(u32::from).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn option(case: &str, number: &str, stressed_letter_index: usize) -> StressOption {
+        StressOption {
+            stress_type: 2,
+            stressed_letter_index,
+            grammatical_case: Some(case.to_string()),
+            number: Some(number.to_string()),
+            ..Default::default()
+        }
+    }
+
+    // Rendering is the part of the crate that works without Python, so whatever the engine (or a
+    // caller) hands it has to come back as a word or an error, never as a panic.
+    proptest::proptest! {
+        #[test]
+        fn rendering_never_panics(word in "\\PC{0,12}", stress_type: u8, index in 0usize..16) {
+            let rendered = create_stresed_word(&word, stress_type, index);
+            if index >= word.chars().count() {
+                proptest::prop_assert_eq!(rendered, Err(out_of_bounds(&word, index)));
+            }
+            let _ = place_stress(&word, stress_type, index, MarkPlacement::Preceding);
+        }
+
+        #[test]
+        fn options_never_panic(
+            word in "[a-zA-ZąčęėįšųūžĄČĘĖĮŠŲŪŽ\u{0300}\u{0301}\u{0303}]{0,12}",
+            stress_type in 0u8..5,
+            engine_index in proptest::prop_oneof![
+                -16i64..16,
+                i64::MAX - 16..=i64::MAX,
+                proptest::num::i64::ANY,
+            ],
+        ) {
+            if let Ok(stressed_letter_index) = resolve_stressed_letter_index(&word, engine_index) {
+                let option = StressOption {
+                    stress_type,
+                    stressed_letter_index,
+                    ..Default::default()
+                };
+                let _ = try_stress_with_option(&word, &option);
+                for mark_placement in [MarkPlacement::Over, MarkPlacement::Preceding] {
+                    let options = AccentuationOptions {
+                        mark_placement,
+                        combining_grave: true,
+                        ..Default::default()
+                    };
+                    let _ = stressed_word(&word, option.clone(), false, &options);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn engine_indices_onto_input_letters() {
+        assert_eq!(input_index("žodį", 1), 1);
+        assert_eq!(input_index("ŽODĮ", 1), 1);
+        // ž and į written with combining carons and ogoneks
+        let decomposed: String = "žodį".nfd().collect();
+        assert_eq!(input_index(&decomposed, 1), 2);
+        assert_eq!(input_index(&decomposed, 3), 4);
+        assert_eq!(input_index(&decomposed, 4), 6);
+    }
+
+    #[test]
+    fn stress_on_decomposed_input() {
+        let decomposed: String = "žodį".nfd().collect();
+        let stressed = create_stresed_word(&decomposed, 2, input_index(&decomposed, 1));
+        assert_eq!(stressed.unwrap().nfc().collect::<String>(), "žõdį");
+
+        let decomposed: String = "Ąžuolas".nfd().collect();
+        let stressed = create_stresed_word(&decomposed, 2, input_index(&decomposed, 0)).unwrap();
+        assert!(stressed.starts_with("Ą̃"));
+        assert_eq!(stressed.nfc().collect::<String>(), "Ą̃žuolas");
+
+        let decomposed: String = "gerą".nfd().collect();
+        let stressed = create_stresed_word(&decomposed, 1, input_index(&decomposed, 3)).unwrap();
+        assert_eq!(stressed, "gerą́");
+        assert_eq!(
+            place_stress(&decomposed, 1, 3, MarkPlacement::Preceding),
+            Ok(String::from("gerˊą"))
+        );
+    }
+
+    #[test]
+    fn diphthongs_keep_their_priegaide() {
+        use NormalizedStress::*;
+        for (word, ordinal, stress_type, rendered, stress) in [
+            ("laukas", 0, 2, "laũkas", Circumflex),
+            ("gerai", 1, 2, "geraĩ", Circumflex),
+            ("diena", 0, 1, "díena", Acute),
+            ("duona", 0, 1, "dúona", Acute),
+            ("pilnas", 0, 2, "pil̃nas", Circumflex),
+        ] {
+            assert_eq!(
+                accentuate_forced(word, ordinal, stress_type).unwrap(),
+                rendered
+            );
+            let index = syllables::nuclei(word)[ordinal].stressed_letter_index(stress_type);
+            assert_eq!(
+                normalize_stress_at(word, index, stress_type),
+                Some(stress),
+                "{word}"
+            );
+        }
+        // a lone i or u is still short, whatever the engine says
+        assert_eq!(normalize_stress_at("gilus", 1, 1), Some(Short));
+        assert_eq!(normalize_stress_at("katu", 3, 2), Some(Short));
+        assert_eq!(normalize_stress_at("laukas", 2, 0), Some(Short));
+        assert_eq!(normalize_stress_at("laukas", 9, 2), None);
+    }
+
+    #[test]
+    fn accented_keeps_its_stress() {
+        let option = StressOption {
+            stress_type: 2,
+            stressed_letter_index: 3,
+            ..Default::default()
+        };
+        let stressed = stressed_word("kelio", option, false, &Default::default()).unwrap();
+        let accented = Accented::new("kelio", stressed);
+        assert_eq!(accented, "keliõ");
+        assert_eq!(accented, String::from("keliõ"));
+        assert_eq!(accented.base(), "kelio");
+        // the mark was moved off the softening i
+        assert_eq!(accented.stressed_letter_index(), 4);
+        assert_eq!(accented.stress_type(), 2);
+        assert_eq!(accented.chars().count(), 5);
+        assert_eq!(accented.to_string(), "keliõ");
+        assert_eq!(String::from(accented.clone()), accented.into_string());
+    }
+
+    #[test]
+    fn index_past_the_end_is_an_error() {
+        let resolver = |_: &str| {
+            Ok(vec![StressOption {
+                stress_type: 2,
+                stressed_letter_index: 4,
+                ..Default::default()
+            }])
+        };
+        let expected = PhonologyError::StressIndexOutOfBounds {
+            word: String::from("gera"),
+            index: 4,
+            word_len: 4,
+        };
+        assert_eq!(
+            accentuate_with(&resolver, "gera", None, None, &Default::default()),
+            Err(expected.clone())
+        );
+        let preceding = AccentuationOptions {
+            mark_placement: MarkPlacement::Preceding,
+            ..Default::default()
+        };
+        assert_eq!(
+            accentuate_with(&resolver, "gera", None, None, &preceding),
+            Err(expected.clone())
+        );
+        assert_eq!(create_stresed_word("gera", 2, 4), Err(expected));
+        assert_eq!(
+            create_stresed_word("", 0, 0).unwrap_err().to_string(),
+            "can't stress letter 0 of word '', which only has 0"
+        );
+    }
+
+    #[test]
+    fn bad_input_is_an_error() {
+        assert_eq!(
+            create_stresed_word("brrr", 1, 1),
+            Err(PhonologyError::UnsupportedStress {
+                word: String::from("brrr"),
+                letter: 'r',
+                stress_type: 1,
+            })
+        );
+        assert!(create_stresed_word("gera", 7, 1).is_err());
+        assert!(place_stress("gera", 7, 1, MarkPlacement::Preceding).is_err());
+        // a stress type the crate doesn't know is an error rather than a panic
+        let option = StressOption {
+            stress_type: 7,
+            stressed_letter_index: 1,
+            ..Default::default()
+        };
+        assert!(try_stress_with_option("gera", &option).is_err());
+        assert!(resolve_stressed_letter_index("gera", i64::MAX).is_err());
+    }
+
+    #[test]
+    fn index_counted_from_the_end() {
+        assert_eq!(resolve_stressed_letter_index("žodis", 1), Ok(1));
+        assert_eq!(resolve_stressed_letter_index("žodis", -4), Ok(1));
+        assert_eq!(resolve_stressed_letter_index("kelio", -2), Ok(3));
+        // past the start of the word, and on a consonant that can't be stressed
+        for index in [5, -6, -5] {
+            assert_eq!(
+                resolve_stressed_letter_index("žodis", index),
+                Err(PhonologyError::InvalidStressIndex {
+                    word: String::from("žodis"),
+                    index
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn lines_skip_blanks_and_stop_at_errors() {
+        struct Failing<'a>(&'a [u8]);
+        impl io::Read for Failing<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                if self.0.is_empty() {
+                    return Err(io::Error::other("disk on fire"));
+                }
+                io::Read::read(&mut self.0, buf)
+            }
+        }
+
+        let reader = io::BufReader::new(Failing(" žodis \n\n  \nnamas\n".as_bytes()));
+        let options = AccentuationOptions::default();
+        let lines: Vec<_> = accentuate_lines(reader, None, None, &options)
+            .map(|line| line.map(|(word, _)| word).map_err(|e| e.to_string()))
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                Ok(String::from("žodis")),
+                Ok(String::from("namas")),
+                Err(String::from("disk on fire"))
+            ]
+        );
+    }
+
+    #[test]
+    fn engine_output_layouts() {
+        Python::with_gil(|py| {
+            let options = |layout: &str| -> Result<usize, String> {
+                let unit = py.eval(layout, None, None).unwrap();
+                let word = first_word(unit).map_err(|e| e.to_string())?;
+                let decoded = follow_path(word, "for the first word", &DECODED_OPTIONS_PATH)
+                    .map_err(|e| e.to_string())?;
+                Ok(decoded.len().unwrap())
+            };
+            let word = r#"{"word": "žodį", "stress_options": {"decoded_options": [{}, {}]}}"#;
+            assert_eq!(options(&format!("[[{word}]]")), Ok(2));
+            assert_eq!(
+                options(&format!(r#"{{"groups": [{{"words": [{word}]}}]}}"#)),
+                Ok(2)
+            );
+            assert_eq!(options(&format!(r#"[{{"words": ({word},)}}]"#)), Ok(2));
+
+            let error = options("[[]]").unwrap_err();
+            assert!(
+                error.contains("process(...)[0] has no item 0 (list)"),
+                "{error}"
+            );
+            let error = options(r#"{"units": []}"#).unwrap_err();
+            assert!(
+                error.contains("process(...) has no 'groups' key (dict)"),
+                "{error}"
+            );
+            let error = options(r#"[[{"word": "žodį"}]]"#).unwrap_err();
+            assert!(
+                error.contains("for the first word has no 'stress_options' key (dict)"),
+                "{error}"
+            );
+        });
+    }
+
+    #[test]
+    fn malformed_options_are_errors() {
+        Python::with_gil(|py| {
+            let option = |fields: &[(&str, PyObject)]| -> HashMap<String, PyObject> {
+                fields
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.clone_ref(py)))
+                    .collect()
+            };
+            let field = |options: HashMap<String, PyObject>| match stress_option_from_decoded(
+                py, "žodį", options,
+            ) {
+                Err(PhonologyError::InvalidField { field, .. }) => Some(field),
+                _ => None,
+            };
+
+            let missing = option(&[("stress_type", 2.into_py(py))]);
+            assert_eq!(field(missing), Some("stressed_letter_index"));
+            let wrong_type = option(&[
+                ("stress_type", "circumflex".into_py(py)),
+                ("stressed_letter_index", 1.into_py(py)),
+            ]);
+            assert_eq!(field(wrong_type), Some("stress_type"));
+            let too_large = option(&[
+                ("stress_type", 300.into_py(py)),
+                ("stressed_letter_index", 1.into_py(py)),
+            ]);
+            assert_eq!(field(too_large), Some("stress_type"));
+            assert_eq!(field(HashMap::new()), Some("stress_type"));
+
+            let out_of_range = option(&[
+                ("stress_type", 2.into_py(py)),
+                ("stressed_letter_index", 40.into_py(py)),
+            ]);
+            assert!(matches!(
+                stress_option_from_decoded(py, "žodį", out_of_range),
+                Err(PhonologyError::InvalidStressIndex { index: 40, .. })
+            ));
+        });
+    }
+
+    #[test]
+    fn pairs_split_on_tabs() {
+        let reader = " žodį\tAccusative \n\nnamas\nkelio\t\tKilmininkas\n".as_bytes();
+        let options = AccentuationOptions::default();
+        let words: Vec<_> = accentuate_pairs(reader, None, &options)
+            .map(|line| line.unwrap().0)
+            .collect();
+        assert_eq!(words, ["žodį", "namas", "kelio"]);
+    }
+
+    #[test]
+    fn default_option_prefers_nominative_singular() {
+        let options = [
+            option("Kilmininkas", "vienaskaita", 0),
+            option("Vardininkas", "daugiskaita", 1),
+            option("Vardininkas", "vienaskaita", 2),
+        ];
+        assert_eq!(default_option(&options), Some(&options[2]));
+        assert_eq!(default_option(&options[..2]), Some(&options[1]));
+        assert_eq!(default_option(&options[..1]), Some(&options[0]));
+        assert_eq!(default_option(&[]), None);
+    }
+
+    #[test]
+    fn guessed_options_are_not_confident() {
+        let mut option = option("Vardininkas", "vienaskaita", 1);
+        assert!(
+            stressed_word("gera", option.clone(), false, &Default::default())
+                .unwrap()
+                .confident
+        );
+
+        option
+            .details
+            .insert(String::from("confidence"), String::from("0.9"));
+        assert!(
+            stressed_word("gera", option.clone(), false, &Default::default())
+                .unwrap()
+                .confident
+        );
+
+        option
+            .details
+            .insert(String::from("guessed"), String::from("true"));
+        assert!(
+            !stressed_word("gera", option.clone(), false, &Default::default())
+                .unwrap()
+                .confident
+        );
+
+        option.details.remove("guessed");
+        option
+            .details
+            .insert(String::from("confidence"), String::from("0.1"));
+        assert!(
+            !stressed_word("gera", option, false, &Default::default())
+                .unwrap()
+                .confident
+        );
+    }
+
+    #[test]
+    fn registered_cases_are_translated_and_kept() {
+        register_case("Allative", "Aliatyvas");
+        assert_eq!(get_case_name("allative"), "Aliatyvas");
+        register_case("allative", "Prieinamasis");
+        assert_eq!(get_case_name("ALLATIVE"), "Prieinamasis");
+        // registering a name again doesn't leak another copy of it
+        let first = registered_case("allative").unwrap();
+        register_case("Allative", "Aliatyvas");
+        register_case("allative", "Prieinamasis");
+        assert!(std::ptr::eq(first, registered_case("allative").unwrap()));
+        // the standard cases come first
+        register_case("nominative", "Pirmininkas");
+        assert_eq!(get_case_name("nominative"), "Vardininkas");
+
+        let options = [
+            option("Vardininkas", "vienaskaita", 1),
+            option("Prieinamasis", "vienaskaita", 3),
+        ];
+        let resolver =
+            |_: &str| -> Result<Vec<StressOption>, PhonologyError> { Ok(options.to_vec()) };
+        let stressed = accentuate_with(
+            &resolver,
+            "namop",
+            Some("allative"),
+            None,
+            &Default::default(),
+        );
+        assert_eq!(stressed.unwrap().option, options[1]);
+        let cases: Vec<String> = accentuations_by_case("namop", &options)
+            .into_iter()
+            .map(|(case, _)| case)
+            .collect();
+        assert_eq!(cases, ["Vardininkas", "Prieinamasis"]);
+    }
+
+    #[test]
+    fn readings_are_overlaid() {
+        let overlay = |readings: &[&str]| {
+            overlay_readings(&readings.iter().map(|r| r.to_string()).collect::<Vec<_>>())
+        };
+        assert_eq!(overlay(&["kãsa", "kasà"]), "kãsà");
+        assert_eq!(overlay(&["kãsa", "kása"]), "kã\u{0301}sa");
+        assert_eq!(overlay(&["vil̃kas", "vilkàs"]), "vil̃kàs");
+        assert_eq!(overlay(&["kãsa"]), "kãsa");
+        assert_eq!(overlay(&[]), "");
+    }
+
+    #[test]
+    fn distinct_accentuations_are_deduplicated() {
+        let options = [
+            option("Vardininkas", "vienaskaita", 3),
+            option("Kilmininkas", "vienaskaita", 1),
+            option("Vardininkas", "daugiskaita", 1),
+        ];
+        assert_eq!(
+            distinct_accentuations("kasa", &options).unwrap(),
+            ["kasã", "kãsa"]
+        );
+    }
+
+    #[test]
+    fn unplaceable_readings_are_errors() {
+        let options = [
+            option("Vardininkas", "vienaskaita", 1),
+            option("Vardininkas", "vienaskaita", 9),
+        ];
+        assert_eq!(
+            distinct_accentuations("kasa", &options),
+            Err(out_of_bounds("kasa", 9))
+        );
+        assert!(readings_in_case("kasa", options.to_vec(), Case::Nominative).is_err());
+    }
+
+    #[test]
+    fn adverbs_ignore_case_and_number() {
+        let resolver = |word: &str| -> Result<Vec<StressOption>, PhonologyError> {
+            let (stress_type, stressed_letter_index) = match word {
+                "gerai" => (2, 4),
+                "geriau" => (2, 5),
+                "geriausiai" => (1, 4),
+                _ => return Ok(Vec::new()),
+            };
+            Ok(vec![StressOption {
+                stress_type,
+                stressed_letter_index,
+                ..Default::default()
+            }])
+        };
+        let options = AccentuationOptions::default();
+        for (word, accented) in [
+            ("gerai", "geraĩ"),
+            ("geriau", "geriaũ"),
+            ("geriausiai", "geriáusiai"),
+        ] {
+            let stressed = accentuate_with(
+                &resolver,
+                word,
+                Some("Vardininkas"),
+                Some("vienaskaita"),
+                &options,
+            )
+            .unwrap();
+            assert_eq!(stressed.accented, accented);
+            assert!(!stressed.fell_back);
+        }
+        assert!(accentuate_with(&resolver, "ir", None, None, &options).is_err());
+    }
+
+    #[test]
+    fn punctuation_is_stripped_and_put_back() {
+        let resolver = |word: &str| -> Result<Vec<StressOption>, PhonologyError> {
+            if word != "gera" {
+                return Ok(Vec::new());
+            }
+            Ok(vec![StressOption {
+                stress_type: 0,
+                stressed_letter_index: 3,
+                ..Default::default()
+            }])
+        };
+        let options = AccentuationOptions {
+            strip_punctuation: true,
+            ..Default::default()
+        };
+        for (word, accented) in [
+            ("gera,", "gerà,"),
+            ("„gera“", "„gerà“"),
+            ("\"gera\"", "\"gerà\""),
+            ("(gera).", "(gerà)."),
+            ("gera", "gerà"),
+        ] {
+            let stressed = accentuate_with(&resolver, word, None, None, &options).unwrap();
+            assert_eq!(stressed.accented, accented);
+        }
+        assert!(accentuate_with(&resolver, "gera,", None, None, &Default::default()).is_err());
+        assert!(accentuate_with(&resolver, "...", None, None, &options).is_err());
+    }
+
+    #[test]
+    fn each_case_has_its_own_result() {
+        let mut options = vec![
+            option("Vardininkas", "vienaskaita", 1),
+            option("Kilmininkas", "vienaskaita", 0),
+            option("Vardininkas", "daugiskaita", 3),
+        ];
+        options[1].stress_type = 0;
+        let cases = accentuations_by_case("žodis", &options);
+        assert_eq!(
+            cases,
+            [
+                (String::from("Vardininkas"), Ok(String::from("žõdis"))),
+                (
+                    String::from("Kilmininkas"),
+                    Err(PhonologyError::UnsupportedStress {
+                        word: String::from("žodis"),
+                        letter: 'ž',
+                        stress_type: 0
+                    })
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn readings_of_homographs() {
+        let mut options = vec![
+            option("Vardininkas", "vienaskaita", 3),
+            option("Kilmininkas", "vienaskaita", 1),
+            option("Vardininkas", "vienaskaita", 1),
+            option("Vardininkas", "daugiskaita", 3),
+        ];
+        options[0].stress_type = 0;
+        options[3].stress_type = 0;
+        assert_eq!(
+            readings_in_case("kasa", options.clone(), Case::Nominative).unwrap(),
+            ["kasà", "kãsa"]
+        );
+        assert!(readings_in_case("kasa", options, Case::Dative)
+            .unwrap()
+            .is_empty());
+
+        let adverb = vec![StressOption {
+            stress_type: 2,
+            stressed_letter_index: 4,
+            ..Default::default()
+        }];
+        assert_eq!(
+            readings_in_case("gerai", adverb, Case::Dative).unwrap(),
+            ["geraĩ"]
+        );
+    }
+
+    #[test]
+    fn find_option_by_case_and_number() {
+        let options = [
+            option("Vardininkas", "vienaskaita", 1),
+            option("Galininkas", "daugiskaita", 2),
+            option("Galininkas", "vienaskaita", 3),
+        ];
+        assert_eq!(find_option(&options, None, None), Some(&options[0]));
+        assert_eq!(
+            find_option(&options, Some("Galininkas"), None),
+            Some(&options[1])
+        );
+        assert_eq!(
+            find_option(&options, Some("Galininkas"), Some("vienaskaita")),
+            Some(&options[2])
+        );
+        assert_eq!(find_option(&options, Some("Vietininkas"), None), None);
+    }
+
+    #[test]
+    fn unknown_case_uses_first_option_unless_strict() {
+        let lenient = AccentuationOptions::default();
+        let strict = AccentuationOptions {
+            strict_cases: true,
+            ..Default::default()
+        };
+        assert_eq!(requested_case(Some("UNKNOWN"), &lenient), None);
+        assert_eq!(requested_case(Some("UNKNOWN"), &strict), Some("UNKNOWN"));
+        assert_eq!(
+            requested_case(Some("Galininkas"), &lenient),
+            Some("Galininkas")
+        );
+        assert_eq!(
+            requested_case(Some("accusative"), &strict),
+            Some("Galininkas")
+        );
+
+        let options = [
+            option("Kilmininkas", "vienaskaita", 1),
+            option("Vardininkas", "vienaskaita", 3),
+        ];
+        assert_eq!(
+            find_option(&options, requested_case(Some("UNKNOWN"), &lenient), None),
+            Some(&options[0])
+        );
+        assert_eq!(
+            find_option(&options, requested_case(Some("UNKNOWN"), &strict), None),
+            None
+        );
+    }
+
+    #[test]
+    fn missing_vocative() {
+        let options = [
+            option("Vardininkas", "vienaskaita", 1),
+            option("Galininkas", "vienaskaita", 2),
+        ];
+        let vocative = Some("Šauksmininkas");
+        assert_eq!(
+            select_option("žodis", &options, vocative, None, &Default::default()),
+            Err(PhonologyError::NoVocative {
+                word: String::from("žodis")
+            })
+        );
+
+        let fallback = AccentuationOptions {
+            vocative_fallback: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            select_option("žodis", &options, vocative, None, &fallback).unwrap(),
+            (&options[0], true)
+        );
+        assert!(matches!(
+            select_option("žodis", &options, Some("Vietininkas"), None, &fallback),
+            Err(PhonologyError::CaseNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn parse_multext_east_noun_tags() {
+        assert_eq!(
+            parse_noun_tag("Ncmsgn"),
+            Some(("Kilmininkas", Some("vienaskaita")))
+        );
+        assert_eq!(
+            parse_noun_tag("Ncfpa"),
+            Some(("Galininkas", Some("daugiskaita")))
+        );
+        assert_eq!(parse_noun_tag("Npm-l"), Some(("Vietininkas", None)));
+        assert_eq!(parse_noun_tag("Ncms"), None);
+        assert_eq!(parse_noun_tag("Afpmsn"), None);
+    }
+
+    #[test]
+    fn closest_option_prefers_generic_number() {
+        let generic = StressOption {
+            number: None,
+            ..option("Galininkas", "", 2)
+        };
+        let options = [
+            option("Galininkas", "vienaskaita", 1),
+            generic.clone(),
+            option("Vardininkas", "daugiskaita", 3),
+        ];
+        assert_eq!(
+            find_closest_option(&options, Some("Galininkas")),
+            Some(&generic)
+        );
+        assert_eq!(
+            find_closest_option(&options[..1], Some("Galininkas")),
+            Some(&options[0])
+        );
+        assert_eq!(find_closest_option(&options, Some("Vietininkas")), None);
+    }
+
+    #[test]
+    fn stress_map_entries_render_base_letter_with_mark() {
+        for stress_type in 0..=2 {
+            let mark = stress_mark(stress_type).unwrap();
+            for (&letter, &stressed) in stress_map(stress_type).unwrap().entries() {
+                let rendered = create_stresed_word(&format!("t{letter}s"), stress_type, 1).unwrap();
+                assert_eq!(rendered, format!("t{stressed}s"));
+                assert_eq!(
+                    rendered.nfc().collect::<String>(),
+                    format!("t{letter}{mark}s").nfc().collect::<String>(),
+                    "stress type {stress_type} on '{letter}'",
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn every_counted_nucleus_can_be_forced() {
+        for word in ["gera", "vilkas", "kelio", "laukas", "Ąžuolas", "pilnas"] {
+            let count = nucleus_count(word);
+            for ordinal in 0..count {
+                assert!(
+                    accentuate_forced(word, ordinal, 0).is_ok()
+                        || accentuate_forced(word, ordinal, 2).is_ok()
+                );
+            }
+            assert!(matches!(
+                accentuate_forced(word, count, 2),
+                Err(PhonologyError::NucleusOutOfRange { nuclei, .. }) if nuclei == count
+            ));
+        }
+    }
+
+    #[test]
+    fn a_tergo_follows_the_lithuanian_alphabet() {
+        // y comes right after į, and č after c, unlike in Unicode
+        assert!(a_tergo_key("ty") > a_tergo_key("tį"));
+        assert!(a_tergo_key("ty") < a_tergo_key("tj"));
+        assert!(a_tergo_key("kač") > a_tergo_key("kac"));
+        assert!(a_tergo_key("kač") < a_tergo_key("kad"));
+        assert_eq!(a_tergo_key("Žõdis"), a_tergo_key("žodis"));
+        assert_eq!(a_tergo_key("ba-1"), "1-AC");
+    }
+
+    #[test]
+    fn codepoints_match_rendered_words() {
+        for stress_type in 0..=2 {
+            for &letter in stress_map(stress_type).unwrap().keys() {
+                for letter in [letter, letter.to_uppercase().next().unwrap()] {
+                    let rendered = create_stresed_word(&letter.to_string(), stress_type, 0);
+                    let codepoints: Vec<u32> = rendered.unwrap().chars().map(u32::from).collect();
+                    assert_eq!(stress_codepoints(stress_type, letter), Some(codepoints));
+                }
+            }
+        }
+        assert_eq!(stress_codepoints(3, 'a'), None);
+    }
+
+    #[test]
+    fn marks_are_in_canonical_order() {
+        assert_eq!(STRESS_TYPE_2.get(&'ų'), Some(&"\u{0173}\u{0303}"));
+        assert_eq!(STRESS_TYPE_2.get(&'ą'), Some(&"\u{0105}\u{0303}"));
+        for stress_type in 0..=2 {
+            for marked in stress_map(stress_type).unwrap().values() {
+                assert_eq!(&reorder_combining_marks(marked), marked);
+            }
+        }
+        assert_eq!(
+            create_stresed_word("kų", 2, 1).unwrap(),
+            "k\u{0173}\u{0303}"
+        );
+        assert_eq!(
+            create_stresed_word("ką", 2, 1).unwrap(),
+            "k\u{0105}\u{0303}"
+        );
+        // an ogonek and a macron, which don't compose into one letter
+        let decomposed = "su\u{0304}\u{0328}";
+        assert_eq!(
+            create_stresed_word(decomposed, 2, 1).unwrap(),
+            "su\u{0328}\u{0304}\u{0303}"
+        );
+        assert_eq!(
+            reorder_combining_marks("\u{0303}a\u{0307}\u{0303}\u{0328}"),
+            "\u{0303}a\u{0328}\u{0307}\u{0303}"
+        );
+    }
+
+    fn stressed(word: &str, stress_type: u8, stressed_letter_index: usize) -> String {
+        let option = StressOption {
+            stress_type,
+            stressed_letter_index,
+            ..Default::default()
+        };
+        try_stress_with_option(word, &option)
+            .unwrap()
+            .nfc()
+            .collect()
+    }
+
+    #[test]
+    fn io_stem_endings() {
+        assert_eq!(stressed("brolis", 1, 2), "brólis");
+        assert_eq!(stressed("brolio", 1, 2), "brólio");
+        assert_eq!(stressed("kelys", 2, 3), "kelỹs");
+        assert_eq!(stressed("kelio", 2, 1), "kẽlio");
+        assert_eq!(stressed("kelias", 2, 1), "kẽlias");
+        assert_eq!(stressed("vėjis", 2, 1), "vė̃jis");
+    }
+
+    #[test]
+    fn stress_on_softening_i_moves_to_ending() {
+        assert_eq!(stressed("keliu", 0, 3), "keliù");
+        assert_eq!(stressed("vaisiui", 0, 4), "vaisiùi");
+        assert_eq!(stressed("kelio", 1, 3), "kelió");
+        // ą can't take short stress, so there's nowhere better to put it
+        assert_eq!(stressed("kelią", 0, 3), "kelìą");
+    }
+
+    #[test]
+    fn sonorant_stress_only_in_mixed_diphthongs() {
+        assert_eq!(stressed("pilnas", 2, 2), "pil̃nas");
+        assert_eq!(stressed("Vilkas", 2, 2), "Vil̃kas");
+        assert_eq!(stressed("kalnas", 2, 2), "kal̃nas");
+        // before a vowel, or after a long vowel, the sonorant can't be stressed
+        assert_eq!(stressed("gilus", 2, 2), "gĩlus");
+        assert_eq!(stressed("kėlnės", 2, 2), "kė̃lnės");
+    }
+
+    #[test]
+    fn mark_before_stressed_letter() {
+        let preceding = AccentuationOptions {
+            mark_placement: MarkPlacement::Preceding,
+            ..Default::default()
+        };
+        let word = |word, option| {
+            stressed_word(word, option, false, &preceding)
+                .unwrap()
+                .accented
+        };
+        assert_eq!(
+            word("Žodį", option("Galininkas", "vienaskaita", 1)),
+            "Ž˜odį"
+        );
+        let mut kelio = option("Kilmininkas", "vienaskaita", 3);
+        kelio.stress_type = 1;
+        assert_eq!(word("kelio", kelio), "keliˊo");
+        let mut gera = option("Vardininkas", "vienaskaita", 3);
+        gera.stress_type = 0;
+        let gera = stressed_word("gera", gera, false, &preceding).unwrap();
+        assert_eq!((gera.accented.as_str(), gera.mark.as_str()), ("gerˋa", "ˋ"));
+    }
+
+    #[test]
+    fn long_vowel_circumflexes() {
+        // each is the precomposed letter and a combining tilde, already in canonical order
+        for letter in ['ą', 'ę', 'ė', 'į', 'ų', 'ū'] {
+            let marked = STRESS_TYPE_2.get(&letter).unwrap();
+            assert_eq!(marked.chars().collect::<Vec<_>>(), [letter, '\u{0303}']);
+            assert_eq!(marked.nfc().collect::<String>(), *marked);
+            assert_eq!(
+                marked.nfd().collect::<String>(),
+                format!("{letter}\u{0303}").nfd().collect::<String>()
+            );
+        }
+
+        let doubled = AccentuationOptions {
+            doubled_long_circumflex: true,
+            ..Default::default()
+        };
+        let circumflex = |index| StressOption {
+            stress_type: 2,
+            stressed_letter_index: index,
+            ..Default::default()
+        };
+        let accented = |word, index, options: &AccentuationOptions| {
+            stressed_word(word, circumflex(index), false, options)
+                .unwrap()
+                .accented
+        };
+        assert_eq!(accented("sūnų", 3, &Default::default()), "sūnų\u{0303}");
+        assert_eq!(accented("sūnų", 3, &doubled), "sūnųũ");
+        assert_eq!(accented("Ūsas", 0, &doubled), "ŪŨsas");
+        assert_eq!(accented("gėlė", 1, &doubled), "gėẽlė");
+        assert_eq!(accented("kąsnis", 1, &doubled), "kąãsnis");
+        // vowels without a mark of their own already have a precomposed tilde
+        assert_eq!(accented("žodis", 1, &doubled), "žõdis");
+        assert_eq!(accented("lygus", 1, &doubled), "lỹgus");
+        let acute = StressOption {
+            stress_type: 1,
+            ..circumflex(1)
+        };
+        let acute = stressed_word("sūnus", acute, false, &doubled).unwrap();
+        assert_eq!(acute.accented, "sū́nus");
+    }
+
+    #[test]
+    fn combining_grave() {
+        let combining = AccentuationOptions {
+            combining_grave: true,
+            ..Default::default()
+        };
+        let short = |index| StressOption {
+            stress_type: 0,
+            stressed_letter_index: index,
+            ..Default::default()
+        };
+        for (word, index) in [("gera", 3), ("Ignas", 0), ("katu", 3), ("rotušė", 1)] {
+            let precomposed = stressed_word(word, short(index), false, &Default::default());
+            let precomposed = precomposed.unwrap().accented;
+            let decomposed = stressed_word(word, short(index), false, &combining);
+            let decomposed = decomposed.unwrap().accented;
+            assert!(decomposed.contains('\u{0300}'), "{decomposed}");
+            assert!(!decomposed.chars().any(|c| "àèìòùÀÈÌÒÙ".contains(c)));
+            assert_eq!(
+                decomposed.nfd().collect::<String>(),
+                precomposed.nfd().collect::<String>()
+            );
+        }
+        let gera = stressed_word("gera", short(3), false, &combining).unwrap();
+        assert_eq!(
+            (gera.accented.as_str(), gera.mark.as_str()),
+            ("gera\u{0300}", "\u{0300}")
+        );
+        // the other stress types aren't changed
+        let mut tilde = short(1);
+        tilde.stress_type = 2;
+        let gera = stressed_word("gera", tilde, false, &combining).unwrap();
+        assert_eq!(gera.accented, "gẽra");
+    }
+
+    #[test]
+    fn stress_maps_cover_every_stressable_letter() {
+        for stress_type in 0..=2 {
+            assert_eq!(
+                uncovered_letters(stress_type),
+                [],
+                "stress type {stress_type}"
+            );
+        }
+        assert_eq!(uncovered_letters(3), []);
+
+        let stressable =
+            |stress_type| -> String { stressable(stress_type).unwrap().into_iter().collect() };
+        assert_eq!(stressable(0), "aeiou");
+        assert_eq!(stressable(1), "aąeęėiįyouųū");
+        assert_eq!(stressable(2), "aąeęėiįylmnoruųū");
+    }
+
+    #[test]
+    fn truncation_keeps_marks_with_their_letters() {
+        // "vil̃kas" is v i l + combining tilde k a s, so 3 chars would cut the tilde off
+        assert_eq!(truncate_graphemes("vil̃kas", 4), "vil̃…");
+        assert_eq!(truncate_graphemes("vil̃kas", 3), "vi…");
+        assert_eq!(truncate_graphemes("katu\u{0300}", 4), "katu\u{0300}");
+        assert_eq!(truncate_graphemes("katu\u{0300}s", 4), "kat…");
+        assert_eq!(truncate_graphemes("gerà", 1), "…");
+        assert_eq!(truncate_graphemes("gerà", 0), "");
+        assert_eq!(truncate_graphemes("", 0), "");
+    }
+
+    #[test]
+    fn uppercase_keeps_marks_on_their_letters() {
+        assert_eq!(to_uppercase_lithuanian("žõdį"), "ŽÕDĮ");
+        assert_eq!(to_uppercase_lithuanian("vil̃kas"), "VIL̃KAS");
+        assert_eq!(to_uppercase_lithuanian("katu\u{0300}"), "KATÙ");
+        assert_eq!(to_uppercase_lithuanian("įi\u{0307}\u{0301}"), "ĮÍ");
+        // the dot only goes away on soft-dotted letters
+        assert_eq!(to_uppercase_lithuanian("ė̃"), "Ė̃");
+    }
+
+    #[test]
+    fn stress_on_first_letter() {
+        assert_eq!(create_stresed_word("ašis", 2, 0), Ok(String::from("ãšis")));
+        assert_eq!(
+            create_stresed_word("ąžuolas", 2, 0),
+            Ok(String::from("ą̃žuolas"))
+        );
+        assert_eq!(create_stresed_word("ūsas", 1, 0), Ok(String::from("ū́sas")));
+    }
+
+    #[test]
+    fn stress_on_capitalized_first_letter() {
+        assert_eq!(create_stresed_word("Ola", 1, 0), Ok(String::from("Óla")));
+        assert_eq!(
+            create_stresed_word("Ąžuolas", 2, 0),
+            Ok(String::from("Ą̃žuolas"))
+        );
+        assert_eq!(
+            create_stresed_word("Ignas", 0, 0),
+            Ok(String::from("Ìgnas"))
+        );
+    }
+
+    #[test]
+    fn proper_nouns() {
+        let resolver = |word: &str| -> Result<Vec<StressOption>, PhonologyError> {
+            let (stress_type, stressed_letter_index) = match word.to_lowercase().as_str() {
+                "kazlauskas" => (2, 5),
+                "mažvydas" => (1, 4),
+                "jonaitis" => (1, 3),
+                "ąžuolas" => (2, 0),
+                _ => return Ok(Vec::new()),
+            };
+            Ok(vec![StressOption {
+                stress_type,
+                stressed_letter_index,
+                grammatical_case: Some(String::from("Vardininkas")),
+                ..Default::default()
+            }])
+        };
+        let accentuate = |word| {
+            accentuate_with(&resolver, word, None, None, &Default::default())
+                .map(|stressed| stressed.accented)
+        };
+        assert_eq!(accentuate("Kazlauskas").unwrap(), "Kazlaũskas");
+        assert_eq!(accentuate("Mažvydas").unwrap(), "Mažvýdas");
+        assert_eq!(accentuate("Jonaitis").unwrap(), "Jonáitis");
+        assert_eq!(accentuate("KAZLAUSKAS").unwrap(), "KAZLAŨSKAS");
+        assert_eq!(accentuate("MAŽVYDAS").unwrap(), "MAŽVÝDAS");
+        assert_eq!(accentuate("ĄŽUOLAS").unwrap(), "Ą̃ŽUOLAS");
+        // capitals inside a name, as in a surname written in a database field
+        assert_eq!(accentuate("MažVydas").unwrap(), "MažVýdas");
+        assert_eq!(
+            accentuate("Petraitis").unwrap_err(),
+            PhonologyError::CaseNotFound {
+                word: String::from("Petraitis"),
+                case: None,
+                number: None,
+            }
+        );
+    }
+
+    #[test]
+    fn stress_after_non_ascii_first_letter() {
+        assert_eq!(
+            create_stresed_word("žodis", 2, 1),
+            Ok(String::from("žõdis"))
+        );
+        assert_eq!(
+            create_stresed_word("Šarūnas", 1, 3),
+            Ok(String::from("Šarū́nas"))
+        );
+    }
 }
@@ -0,0 +1,177 @@
+use std::{
+    env,
+    fs::File,
+    io::{self, BufRead, BufReader},
+    process::ExitCode,
+};
+
+use lithuanian_phonology::{
+    accentuate_lines, accentuate_pairs, check_environment, AccentuationOptions, Case,
+};
+#[cfg(feature = "serde")]
+use lithuanian_phonology::{PhonologyError, StressedWord};
+
+const USAGE: &str = "\
+usage: lithuanian-phonology [--case CASE] [--file PATH | --pairs PATH] [--jsonl]
+       lithuanian-phonology --doctor
+
+Accentuates one word per line, read from PATH or from stdin, and prints each word with its accent
+marks. CASE is an English or Lithuanian case name, such as genitive or Kilmininkas.
+
+With --pairs, each line of PATH is a word and a case name separated by a tab, and each word is
+accentuated for its own case instead of CASE.
+
+With --jsonl, each word is printed as a JSON object with its stress option instead, or as
+{\"word\": ..., \"error\": ...} if it couldn't be accentuated. This needs the serde feature.
+
+With --doctor, nothing is accentuated: it checks that Python, phonology_engine and its engine can
+all be started, and prints what it found or what is missing.";
+
+struct Args {
+    case: Option<Case>,
+    file: Option<String>,
+    pairs: bool,
+    doctor: bool,
+    #[cfg(feature = "serde")]
+    jsonl: bool,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args {
+        case: None,
+        file: None,
+        pairs: false,
+        doctor: false,
+        #[cfg(feature = "serde")]
+        jsonl: false,
+    };
+    let mut raw = env::args().skip(1);
+    while let Some(arg) = raw.next() {
+        match arg.as_str() {
+            "--case" => {
+                let name = raw.next().ok_or("--case needs a case name")?;
+                args.case = Some(Case::from_name(&name).ok_or(format!("'{name}' is not a case"))?);
+            }
+            "--file" | "--pairs" if args.file.is_some() => {
+                return Err(String::from("only one of --file and --pairs can be given"))
+            }
+            "--file" => args.file = Some(raw.next().ok_or("--file needs a path")?),
+            "--pairs" => {
+                args.file = Some(raw.next().ok_or("--pairs needs a path")?);
+                args.pairs = true;
+            }
+            "--doctor" => args.doctor = true,
+            #[cfg(feature = "serde")]
+            "--jsonl" => args.jsonl = true,
+            #[cfg(not(feature = "serde"))]
+            "--jsonl" => return Err(String::from("--jsonl needs the serde feature")),
+            "-h" | "--help" => return Err(String::new()),
+            _ => return Err(format!("unexpected argument '{arg}'")),
+        }
+    }
+    Ok(args)
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(message) => {
+            if !message.is_empty() {
+                eprintln!("{message}");
+            }
+            eprintln!("{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    if args.doctor {
+        return doctor();
+    }
+
+    let reader: Box<dyn BufRead> = match &args.file {
+        Some(path) => match File::open(path) {
+            Ok(file) => Box::new(BufReader::new(file)),
+            Err(e) => {
+                eprintln!("unable to open '{path}': {e}");
+                return ExitCode::from(2);
+            }
+        },
+        None => Box::new(io::stdin().lock()),
+    };
+
+    // unknown names in a pairs file fail instead of quietly using the first option
+    let options = AccentuationOptions {
+        strict_cases: args.pairs,
+        ..Default::default()
+    };
+    let case = args.case.map(Case::lithuanian_name);
+    let lines: Box<dyn Iterator<Item = _>> = if args.pairs {
+        Box::new(accentuate_pairs(reader, None, &options))
+    } else {
+        Box::new(accentuate_lines(reader, case, None, &options))
+    };
+    let (mut succeeded, mut failed) = (0, 0);
+    for line in lines {
+        match line {
+            #[cfg(feature = "serde")]
+            Ok((word, stressed)) if args.jsonl => {
+                match &stressed {
+                    Ok(_) => succeeded += 1,
+                    Err(_) => failed += 1,
+                }
+                println!("{}", json_line(&word, &stressed));
+            }
+            Ok((_, Ok(stressed))) => {
+                succeeded += 1;
+                println!("{}", stressed.accented);
+            }
+            // the word is still printed, so the output lines up with the input
+            Ok((word, Err(e))) => {
+                failed += 1;
+                eprintln!("{e}");
+                println!("{word}");
+            }
+            Err(e) => {
+                eprintln!("unable to read input: {e}");
+                return ExitCode::from(2);
+            }
+        }
+    }
+
+    eprintln!("{succeeded} accentuated, {failed} failed");
+    if failed > 0 {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn doctor() -> ExitCode {
+    match check_environment() {
+        Ok(info) => {
+            println!("Python {}", info.python_version);
+            match info.module_path {
+                Some(path) => println!("phonology_engine from {path}"),
+                None => println!("phonology_engine (built in)"),
+            }
+            println!("the engine started");
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+fn json_line(word: &str, stressed: &Result<StressedWord, PhonologyError>) -> String {
+    let value = match stressed {
+        Ok(stressed) => serde_json::to_value(stressed),
+        Err(e) => Ok(serde_json::json!({ "word": word, "error": e })),
+    };
+    value.map_or_else(
+        |e| serde_json::json!({ "word": word, "error": e.to_string() }).to_string(),
+        |value| value.to_string(),
+    )
+}
@@ -0,0 +1,107 @@
+use crate::{
+    graphemes, select_option, stressed_grapheme, text, try_stress_with_option, Case,
+    PhonologyEngine, PhonologyError, StressOption, StressResolver,
+};
+
+/// Takes a word and returns it for Markdown, accented for the case given and with the stressed
+/// letter in bold, like `ž**õ**dį`, so that it can be pasted into notes and tables without the
+/// stress getting lost when a renderer or font mangles the combining marks.
+///
+/// The bold wraps the whole stressed grapheme, its marks included, and uses asterisks, which
+/// CommonMark allows in the middle of a word. Characters that mean something in Markdown, like
+/// `*`, `_`, the `|` of tables or the `-` of lists, are escaped with a backslash. A word the
+/// engine has no stress options for at all is returned escaped and without bold, while one that
+/// only has options in other cases is a [`PhonologyError::CaseNotFound`] error, as with
+/// [`crate::to_ruby_html`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::{to_markdown, Case};
+///
+/// assert_eq!(to_markdown("žodį", Case::Accusative).unwrap(), "ž**õ**dį");
+/// ```
+pub fn to_markdown(word: &str, case: Case) -> Result<String, PhonologyError> {
+    to_markdown_with(&PhonologyEngine, word, case)
+}
+
+/// Like [`to_markdown`], but gets the stress options from `resolver` instead of the engine.
+pub fn to_markdown_with(
+    resolver: &impl StressResolver,
+    word: &str,
+    case: Case,
+) -> Result<String, PhonologyError> {
+    let stress_options = resolver.stress_options(word)?;
+    if stress_options.is_empty() {
+        return Ok(text::escape_markdown(word));
+    }
+    let (option, _) = select_option(
+        word,
+        &stress_options,
+        Some(case.lithuanian_name()),
+        None,
+        &Default::default(),
+    )?;
+    markdown_with_option(word, option)
+}
+
+fn markdown_with_option(word: &str, option: &StressOption) -> Result<String, PhonologyError> {
+    let accented = try_stress_with_option(word, option)?;
+    let stressed = stressed_grapheme(word, option);
+    let mut markdown = String::with_capacity(accented.len() + 4);
+    for (i, grapheme) in graphemes(&accented).enumerate() {
+        let escaped = text::escape_markdown(grapheme);
+        if i == stressed {
+            markdown.push_str(&format!("**{escaped}**"));
+        } else {
+            markdown.push_str(&escaped);
+        }
+    }
+    Ok(markdown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn option(stress_type: u8, stressed_letter_index: usize) -> StressOption {
+        StressOption {
+            stress_type,
+            stressed_letter_index,
+            grammatical_case: Some(String::from("Vardininkas")),
+            ..Default::default()
+        }
+    }
+
+    fn resolver(word: &str) -> Result<Vec<StressOption>, PhonologyError> {
+        Ok(match word {
+            "vilkas" => vec![option(2, 2)],
+            "ąžuolas" => vec![option(2, 0)],
+            "juodai-baltas" => vec![option(1, 8)],
+            _ => Vec::new(),
+        })
+    }
+
+    #[test]
+    fn bolds_the_stressed_grapheme() {
+        let markdown = |word| to_markdown_with(&resolver, word, Case::Nominative);
+        assert_eq!(markdown("vilkas").unwrap(), "vi**l̃**kas");
+        assert_eq!(markdown("ąžuolas").unwrap(), "**ą̃**žuolas");
+        assert_eq!(markdown("juodai-baltas").unwrap(), "juodai\\-b**á**ltas");
+        assert!(matches!(
+            to_markdown_with(&resolver, "vilkas", Case::Genitive),
+            Err(PhonologyError::CaseNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn escapes_markdown() {
+        let markdown = |word| to_markdown_with(&resolver, word, Case::Nominative);
+        assert_eq!(markdown("R&D_1").unwrap(), "R\\&D\\_1");
+        // a decomposed letter and its marks are bolded together
+        assert_eq!(
+            markdown_with_option("a\u{0328}žuolas", &option(2, 0)).unwrap(),
+            "**\u{0105}\u{0303}**žuolas"
+        );
+    }
+}
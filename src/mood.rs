@@ -0,0 +1,169 @@
+use crate::{stressed_word, PhonologyEngine, PhonologyError, StressResolver, StressedWord};
+
+/// Grammatical mood of a verb form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Mood {
+    /// `daro`, `darė`, `darys`
+    Indicative,
+    /// `darytų`
+    Conditional,
+    /// `daryk`, `darykime`
+    Imperative,
+    /// `tedaro`, the archaic forms with `te-`
+    Optative,
+}
+
+impl Mood {
+    /// Every mood, in the order above.
+    pub const ALL: [Mood; 4] = [
+        Mood::Indicative,
+        Mood::Conditional,
+        Mood::Imperative,
+        Mood::Optative,
+    ];
+
+    /// The Lithuanian name of the mood, such as `liepiamoji`.
+    pub fn lithuanian_name(self) -> &'static str {
+        match self {
+            Mood::Indicative => "tiesioginė",
+            Mood::Conditional => "tariamoji",
+            Mood::Imperative => "liepiamoji",
+            Mood::Optative => "geidžiamoji",
+        }
+    }
+
+    /// The lowercase english name of the mood, such as `imperative`.
+    pub fn english_name(self) -> &'static str {
+        match self {
+            Mood::Indicative => "indicative",
+            Mood::Conditional => "conditional",
+            Mood::Imperative => "imperative",
+            Mood::Optative => "optative",
+        }
+    }
+
+    /// Looks up a mood by its english or Lithuanian name, ignoring capitalization. Abbreviations
+    /// of the Lithuanian names like `liep. n.` work too, as do the `tiesioginė nuosaka` style names
+    /// with the word for mood after them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use lithuanian_phonology::Mood;
+    ///
+    /// assert_eq!(Mood::from_name("Imperative"), Some(Mood::Imperative));
+    /// assert_eq!(Mood::from_name("liepiamoji nuosaka"), Some(Mood::Imperative));
+    /// assert_eq!(Mood::from_name("tar. n."), Some(Mood::Conditional));
+    /// assert_eq!(Mood::from_name("būtasis"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Mood> {
+        let name = name.trim().to_lowercase();
+        Mood::ALL.into_iter().find(|mood| {
+            let prefix = match mood {
+                Mood::Indicative => "ties",
+                Mood::Conditional => "tar",
+                Mood::Imperative => "liep",
+                Mood::Optative => "geid",
+            };
+            name == mood.english_name() || name.starts_with(prefix)
+        })
+    }
+}
+
+/// Returns a verb form with lithuanian accent marks in the given mood, for forms like the
+/// imperative `daryk` that have their own stress but no case to ask for them by.
+///
+/// The mood comes from the `mood` field of the engine's stress options (see
+/// [`crate::StressOption::mood`]). Options without one, like those of nouns, never match. What the
+/// engine decodes here isn't documented: if it doesn't give moods for a word, that is a
+/// [`PhonologyError::MoodNotFound`] error, and [`crate::get_stress_options`] is the way to see
+/// what it did give.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::{accentuate_mood, Mood};
+///
+/// assert_eq!(accentuate_mood("daryk", Mood::Imperative).unwrap().accented, "darýk");
+/// ```
+pub fn accentuate_mood(word: &str, mood: Mood) -> Result<StressedWord, PhonologyError> {
+    accentuate_mood_with(&PhonologyEngine, word, mood)
+}
+
+/// Like [`accentuate_mood`], but gets the stress options from `resolver` instead of the engine.
+pub fn accentuate_mood_with(
+    resolver: &impl StressResolver,
+    word: &str,
+    mood: Mood,
+) -> Result<StressedWord, PhonologyError> {
+    let options = resolver.stress_options(word)?;
+    let option = options
+        .iter()
+        .find(|option| option.mood() == Some(mood))
+        .ok_or_else(|| PhonologyError::MoodNotFound {
+            word: word.to_string(),
+            mood: mood.english_name(),
+        })?;
+    stressed_word(word, option.clone(), false, &Default::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StressOption;
+
+    fn option(mood: &str, stress_type: u8, stressed_letter_index: usize) -> StressOption {
+        StressOption {
+            stress_type,
+            stressed_letter_index,
+            details: [(String::from("mood"), String::from(mood))].into(),
+            ..Default::default()
+        }
+    }
+
+    fn resolver(word: &str) -> Result<Vec<StressOption>, PhonologyError> {
+        Ok(match word {
+            "daryk" => vec![option("liepiamoji", 1, 3)],
+            "darykime" => vec![option("liepiamoji", 1, 3)],
+            // the past tense and the conditional are spelled differently, so only a made up
+            // homograph can have both
+            "dare" => vec![option("tiesioginė", 0, 1), option("tariamoji", 2, 3)],
+            _ => vec![StressOption::default()],
+        })
+    }
+
+    #[test]
+    fn imperatives() {
+        for (word, accented) in [("daryk", "darýk"), ("darykime", "darýkime")] {
+            let stressed = accentuate_mood_with(&resolver, word, Mood::Imperative).unwrap();
+            assert_eq!(stressed.accented, accented);
+            assert_eq!(stressed.option.mood(), Some(Mood::Imperative));
+        }
+    }
+
+    #[test]
+    fn picks_the_option_in_the_mood() {
+        let stressed = accentuate_mood_with(&resolver, "dare", Mood::Conditional).unwrap();
+        assert_eq!(stressed.accented, "darẽ");
+        let stressed = accentuate_mood_with(&resolver, "dare", Mood::Indicative).unwrap();
+        assert_eq!(stressed.accented, "dàre");
+    }
+
+    #[test]
+    fn options_without_mood_never_match() {
+        assert_eq!(
+            accentuate_mood_with(&resolver, "namas", Mood::Indicative),
+            Err(PhonologyError::MoodNotFound {
+                word: String::from("namas"),
+                mood: "indicative",
+            })
+        );
+        assert_eq!(
+            accentuate_mood_with(&resolver, "daryk", Mood::Optative)
+                .unwrap_err()
+                .to_string(),
+            "no stress option in the optative mood in word 'daryk'"
+        );
+    }
+}
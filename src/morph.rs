@@ -0,0 +1,207 @@
+use crate::{
+    try_stress_with_option, Case, Gender, Mood, Number, PhonologyEngine, PhonologyError,
+    StressOption, StressResolver,
+};
+
+/// What a morphological tagger knows about a word in running text, for
+/// [`accentuate_analyzed`].
+///
+/// Each of the grammatical fields that is set has to match the stress option that gets used, so
+/// the more of them a tagger fills in, the fewer readings are left to choose between:
+///
+/// - `case` is the engine's `grammatical_case`, by its Lithuanian name (see
+///   [`Case::lithuanian_name`])
+/// - `number` is the engine's `number` (see [`Number::lithuanian_name`])
+/// - `gender` is the engine's `gender`, matched with [`Gender::matches`]
+/// - `mood` is the engine's `mood` detail (see [`StressOption::mood`])
+///
+/// The engine doesn't report lemmas or parts of speech, so `lemma` and `pos` can't be matched
+/// against its readings. `pos` is only used to tell which words don't inflect: for a Universal
+/// Dependencies tag of one of them (`ADV`, `ADP`, `CCONJ`, `SCONJ`, `PART` or `INTJ`) the
+/// grammatical fields are ignored, since the engine gives those words without any.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MorphAnalysis {
+    /// The word as it appears in the text.
+    pub form: String,
+    /// The dictionary form of the word, such as `žodis` for `žodį`.
+    pub lemma: Option<String>,
+    /// The part of speech, as a Universal Dependencies tag such as `NOUN`.
+    pub pos: Option<String>,
+    pub case: Option<Case>,
+    pub number: Option<Number>,
+    pub gender: Option<Gender>,
+    pub mood: Option<Mood>,
+}
+
+impl MorphAnalysis {
+    /// Whether a stress option agrees with every field of the analysis that is set.
+    pub fn matches(&self, option: &StressOption) -> bool {
+        if self.is_uninflected() {
+            return true;
+        }
+        let agrees = |expected: Option<bool>| expected.unwrap_or(true);
+        agrees(
+            self.case
+                .map(|case| option.grammatical_case.as_deref() == Some(case.lithuanian_name())),
+        ) && agrees(self.number.map(|number| {
+            option
+                .number
+                .as_deref()
+                .and_then(Number::from_lithuanian_name)
+                == Some(number)
+        })) && agrees(self.gender.map(|gender| {
+            option
+                .gender
+                .as_deref()
+                .is_some_and(|name| gender.matches(name))
+        })) && agrees(self.mood.map(|mood| option.mood() == Some(mood)))
+    }
+
+    fn is_uninflected(&self) -> bool {
+        self.pos.as_deref().is_some_and(|pos| {
+            ["ADV", "ADP", "CCONJ", "SCONJ", "PART", "INTJ"].contains(&pos.to_uppercase().as_str())
+        })
+    }
+}
+
+/// Takes a word a tagger has already analyzed, and returns it with lithuanian accent marks,
+/// using the first stress option that agrees with the analysis (see [`MorphAnalysis`]).
+///
+/// Unlike [`crate::accentuate`], this never falls back to an option that doesn't match: if the
+/// engine has no reading that agrees with everything in the analysis, that is a
+/// [`PhonologyError::CaseNotFound`] error.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::{accentuate_analyzed, Case, MorphAnalysis, Number};
+///
+/// let analysis = MorphAnalysis {
+///     form: String::from("galvos"),
+///     lemma: Some(String::from("galva")),
+///     pos: Some(String::from("NOUN")),
+///     case: Some(Case::Nominative),
+///     number: Some(Number::Plural),
+///     ..Default::default()
+/// };
+/// assert_eq!(accentuate_analyzed(&analysis).unwrap(), "gálvos");
+/// ```
+pub fn accentuate_analyzed(analysis: &MorphAnalysis) -> Result<String, PhonologyError> {
+    accentuate_analyzed_with(&PhonologyEngine, analysis)
+}
+
+/// Like [`accentuate_analyzed`], but gets the stress options from `resolver` instead of the
+/// engine.
+pub fn accentuate_analyzed_with(
+    resolver: &impl StressResolver,
+    analysis: &MorphAnalysis,
+) -> Result<String, PhonologyError> {
+    let word = &analysis.form;
+    let options = resolver.stress_options(word)?;
+    let option = options
+        .iter()
+        .find(|option| analysis.matches(option))
+        .ok_or_else(|| PhonologyError::CaseNotFound {
+            word: word.clone(),
+            case: analysis.case.map(|case| case.lithuanian_name().to_string()),
+            number: analysis
+                .number
+                .map(|number| number.lithuanian_name().to_string()),
+        })?;
+    try_stress_with_option(word, option)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn option(
+        case: Case,
+        number: Number,
+        stress_type: u8,
+        stressed_letter_index: usize,
+    ) -> StressOption {
+        StressOption {
+            stress_type,
+            stressed_letter_index,
+            grammatical_case: Some(case.lithuanian_name().to_string()),
+            number: Some(number.lithuanian_name().to_string()),
+            gender: Some(String::from("moteriškoji")),
+            ..Default::default()
+        }
+    }
+
+    fn resolver(word: &str) -> Result<Vec<StressOption>, PhonologyError> {
+        Ok(match word {
+            "galvos" => vec![
+                option(Case::Genitive, Number::Singular, 2, 4),
+                option(Case::Nominative, Number::Plural, 1, 1),
+                option(Case::Accusative, Number::Plural, 1, 1),
+            ],
+            _ => vec![StressOption {
+                stress_type: 2,
+                stressed_letter_index: 4,
+                ..Default::default()
+            }],
+        })
+    }
+
+    fn galvos() -> MorphAnalysis {
+        MorphAnalysis {
+            form: String::from("galvos"),
+            lemma: Some(String::from("galva")),
+            pos: Some(String::from("NOUN")),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn picks_the_matching_reading() {
+        let accentuate = |analysis| accentuate_analyzed_with(&resolver, &analysis);
+        let genitive = MorphAnalysis {
+            case: Some(Case::Genitive),
+            ..galvos()
+        };
+        assert_eq!(accentuate(genitive).unwrap(), "galvõs");
+        let plural = MorphAnalysis {
+            number: Some(Number::Plural),
+            gender: Some(Gender::Feminine),
+            ..galvos()
+        };
+        assert_eq!(accentuate(plural).unwrap(), "gálvos");
+        assert_eq!(accentuate(galvos()).unwrap(), "galvõs");
+    }
+
+    #[test]
+    fn no_matching_reading() {
+        let analysis = MorphAnalysis {
+            case: Some(Case::Genitive),
+            number: Some(Number::Plural),
+            ..galvos()
+        };
+        assert!(matches!(
+            accentuate_analyzed_with(&resolver, &analysis),
+            Err(PhonologyError::CaseNotFound { case: Some(case), .. }) if case == "Kilmininkas"
+        ));
+        let masculine = MorphAnalysis {
+            gender: Some(Gender::Masculine),
+            ..galvos()
+        };
+        assert!(accentuate_analyzed_with(&resolver, &masculine).is_err());
+    }
+
+    #[test]
+    fn uninflected_words_ignore_the_grammar() {
+        let analysis = MorphAnalysis {
+            form: String::from("gerai"),
+            pos: Some(String::from("adv")),
+            case: Some(Case::Dative),
+            ..Default::default()
+        };
+        assert_eq!(
+            accentuate_analyzed_with(&resolver, &analysis).unwrap(),
+            "geraĩ"
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use crate::{
+    offline_format::{self, StoredOption},
+    Case, Number, PhonologyError, StressOption, StressResolver,
+};
+
+static TABLE: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/offline_stress.bin"));
+
+/// Looks a word up in the stress table built into the crate, without Python. `None` for words that
+/// aren't in it.
+///
+/// The table is made by the build script from `data/offline.tsv`, or from the file the
+/// `LITHUANIAN_PHONOLOGY_OFFLINE_TABLE` environment variable names, which has one stress option
+/// per line: the word, the stress type, the stressed letter index, and optionally the case and
+/// number names, separated by tabs. It's stored in a compact binary format (version 1, described
+/// in `src/offline_format.rs`) that is searched in place, so even tables with hundreds of
+/// thousands of words don't slow down compiling or starting up. Options come back without
+/// [`StressOption::gender`] or [`StressOption::details`], which the table doesn't store.
+///
+/// A word with capitals that isn't in the table is looked up again in lowercase, so that a
+/// capitalized word at the start of a sentence, or a name written in capitals, is still found.
+/// The indices stay right for it, since Lithuanian letters keep their length in lowercase.
+///
+/// # Examples
+///
+/// ```
+/// use lithuanian_phonology::lookup_offline;
+///
+/// let options = lookup_offline("žodį").unwrap();
+/// assert_eq!(options[0].grammatical_case.as_deref(), Some("Galininkas"));
+/// assert!(lookup_offline("nežinomas").is_none());
+/// assert_eq!(lookup_offline("ŽODĮ"), lookup_offline("žodį"));
+/// ```
+pub fn lookup_offline(word: &str) -> Option<Vec<StressOption>> {
+    let options = offline_format::lookup(TABLE, word).or_else(|| {
+        let lowercase = word.to_lowercase();
+        let same_letters = lowercase.chars().count() == word.chars().count();
+        (lowercase != word && same_letters)
+            .then(|| offline_format::lookup(TABLE, &lowercase))
+            .flatten()
+    })?;
+    Some(options.into_iter().map(stress_option).collect())
+}
+
+fn stress_option(stored: StoredOption) -> StressOption {
+    let case = (stored.case as usize)
+        .checked_sub(1)
+        .and_then(|i| Case::ALL.get(i));
+    let number = (stored.number as usize)
+        .checked_sub(1)
+        .and_then(|i| Number::ALL.get(i));
+    StressOption {
+        stress_type: stored.stress_type,
+        stressed_letter_index: stored.stressed_letter_index.into(),
+        grammatical_case: case.map(|case| case.lithuanian_name().to_string()),
+        number: number.map(|number| number.lithuanian_name().to_string()),
+        ..Default::default()
+    }
+}
+
+/// A [`StressResolver`] that uses [`lookup_offline`], for accentuating words without Python. Words
+/// that aren't in the table have no options.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OfflineTable;
+
+impl StressResolver for OfflineTable {
+    fn stress_options(&self, word: &str) -> Result<Vec<StressOption>, PhonologyError> {
+        Ok(lookup_offline(word).unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accentuate_with;
+
+    #[test]
+    fn built_in_table() {
+        let stressed = accentuate_with(
+            &OfflineTable,
+            "žodį",
+            Some("Galininkas"),
+            Some("vienaskaita"),
+            &Default::default(),
+        );
+        assert_eq!(stressed.unwrap().accented, "žõdį");
+        let options = lookup_offline("gerai").unwrap();
+        assert_eq!(options[0].grammatical_case, None);
+        assert_eq!(options[0].number, None);
+    }
+
+    #[test]
+    fn capitalized_words() {
+        let stressed = accentuate_with(&OfflineTable, "Namas", None, None, &Default::default());
+        assert_eq!(stressed.unwrap().accented, "Nãmas");
+        assert_eq!(lookup_offline("VILKAS"), lookup_offline("vilkas"));
+        assert!(lookup_offline("Nežinomas").is_none());
+    }
+}
@@ -0,0 +1,213 @@
+//! The binary format of the offline stress table. This file is also included by the build script,
+//! which writes the table, so it can't use anything from the rest of the crate.
+//!
+//! Version 1 of the format, all integers little endian:
+//!
+//! 1. the magic bytes `LTST`
+//! 2. the format version, `1` (2 bytes)
+//! 3. the number of words (4 bytes)
+//! 4. for each word, in byte order of the words, where its entry starts, counted from the start of
+//!    the table (4 bytes)
+//! 5. the entries
+//!
+//! Each entry is the word's length in bytes (2), the word in UTF-8, the number of options (1), and
+//! then 5 bytes per option: the stress type, the stressed letter index (2), the case (0 for none,
+//! then 1 to 7 for the cases in the order of `Case::ALL`) and the number (0 for none, 1 for
+//! singular, 2 for plural). Readers must reject versions they don't know; a version 2 would only be
+//! needed for changes that version 1 readers would misread.
+
+use std::{collections::BTreeMap, fmt};
+
+pub(crate) const MAGIC: &[u8; 4] = b"LTST";
+pub(crate) const VERSION: u16 = 1;
+const HEADER_LEN: usize = 10;
+
+/// One stress option as it's stored in the table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct StoredOption {
+    pub stress_type: u8,
+    pub stressed_letter_index: u16,
+    /// 0 for none, or the position of the case in the standard order, counting from 1.
+    pub case: u8,
+    /// 0 for none, 1 for singular and 2 for plural.
+    pub number: u8,
+}
+
+/// A word that doesn't fit in the table. `what` is the field it overflows, like `options`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TooLarge {
+    pub word: String,
+    pub what: &'static str,
+}
+
+impl fmt::Display for TooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "word '{}' doesn't fit in the offline table: too many {}",
+            self.word, self.what
+        )
+    }
+}
+
+/// Writes a table. The words don't have to be sorted or unique; later options for a word are
+/// added after the earlier ones. Fails if a word has more options or bytes than its entry can
+/// count, or if the table grows past what its offsets can point to.
+// Only the build script and the tests write tables.
+#[allow(dead_code)]
+pub(crate) fn encode(entries: &[(String, Vec<StoredOption>)]) -> Result<Vec<u8>, TooLarge> {
+    // `str`s are ordered by their bytes, which is the order the table needs.
+    let mut merged: BTreeMap<&str, Vec<StoredOption>> = BTreeMap::new();
+    for (word, options) in entries {
+        merged.entry(word).or_default().extend(options);
+    }
+    let too_large = |word: &str, what| TooLarge {
+        word: word.to_string(),
+        what,
+    };
+
+    let mut table = Vec::new();
+    table.extend_from_slice(MAGIC);
+    table.extend_from_slice(&VERSION.to_le_bytes());
+    let count = u32::try_from(merged.len())
+        .map_err(|_| too_large(merged.keys().last().copied().unwrap_or_default(), "words"))?;
+    table.extend_from_slice(&count.to_le_bytes());
+    let offsets_start = table.len();
+    table.resize(offsets_start + 4 * merged.len(), 0);
+    for (i, (word, options)) in merged.iter().enumerate() {
+        let offset = u32::try_from(table.len()).map_err(|_| too_large(word, "table bytes"))?;
+        let len = u16::try_from(word.len()).map_err(|_| too_large(word, "bytes"))?;
+        let option_count = u8::try_from(options.len()).map_err(|_| too_large(word, "options"))?;
+        table[offsets_start + 4 * i..offsets_start + 4 * i + 4]
+            .copy_from_slice(&offset.to_le_bytes());
+        table.extend_from_slice(&len.to_le_bytes());
+        table.extend_from_slice(word.as_bytes());
+        table.push(option_count);
+        for option in options {
+            table.push(option.stress_type);
+            table.extend_from_slice(&option.stressed_letter_index.to_le_bytes());
+            table.push(option.case);
+            table.push(option.number);
+        }
+    }
+    Ok(table)
+}
+
+/// Checks the header of a table, returning the number of words, or `None` if it isn't a table
+/// this version of the crate can read.
+pub(crate) fn word_count(table: &[u8]) -> Option<usize> {
+    let header = table.get(..HEADER_LEN)?;
+    if &header[..4] != MAGIC || u16::from_le_bytes([header[4], header[5]]) != VERSION {
+        return None;
+    }
+    let count = u32::from_le_bytes(header[6..10].try_into().ok()?) as usize;
+    (table.len() >= HEADER_LEN + 4 * count).then_some(count)
+}
+
+/// Finds a word's options with a binary search over the table, without parsing the rest of it.
+/// `None` for words that aren't in it and for tables [`word_count`] rejects or that are cut off.
+pub(crate) fn lookup(table: &[u8], word: &str) -> Option<Vec<StoredOption>> {
+    let count = word_count(table)?;
+    let entry_word = |i: usize| -> Option<(&[u8], usize)> {
+        let at = HEADER_LEN + 4 * i;
+        let offset = u32::from_le_bytes(table.get(at..at + 4)?.try_into().ok()?) as usize;
+        let len = u16::from_le_bytes(table.get(offset..offset + 2)?.try_into().ok()?) as usize;
+        let start = offset + 2;
+        Some((table.get(start..start + len)?, start + len))
+    };
+
+    let (mut low, mut high) = (0, count);
+    while low < high {
+        let middle = (low + high) / 2;
+        let (entry, options_at) = entry_word(middle)?;
+        match entry.cmp(word.as_bytes()) {
+            std::cmp::Ordering::Less => low = middle + 1,
+            std::cmp::Ordering::Greater => high = middle,
+            std::cmp::Ordering::Equal => {
+                let option_count = *table.get(options_at)? as usize;
+                let bytes = table.get(options_at + 1..options_at + 1 + 5 * option_count)?;
+                return Some(
+                    bytes
+                        .chunks_exact(5)
+                        .map(|option| StoredOption {
+                            stress_type: option[0],
+                            stressed_letter_index: u16::from_le_bytes([option[1], option[2]]),
+                            case: option[3],
+                            number: option[4],
+                        })
+                        .collect(),
+                );
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn option(stress_type: u8, stressed_letter_index: u16, case: u8) -> StoredOption {
+        StoredOption {
+            stress_type,
+            stressed_letter_index,
+            case,
+            number: 1,
+        }
+    }
+
+    #[test]
+    fn written_tables_can_be_read() {
+        let table = encode(&[
+            (String::from("žodis"), vec![option(2, 1, 1)]),
+            (String::from("namas"), vec![option(2, 1, 1)]),
+            (String::from("žodį"), vec![option(2, 1, 4)]),
+            (String::from("žodis"), vec![option(0, 3, 5)]),
+        ])
+        .unwrap();
+        assert_eq!(word_count(&table), Some(3));
+        assert_eq!(
+            lookup(&table, "žodis"),
+            Some(vec![option(2, 1, 1), option(0, 3, 5)])
+        );
+        assert_eq!(lookup(&table, "namas"), Some(vec![option(2, 1, 1)]));
+        assert_eq!(lookup(&table, "žodį"), Some(vec![option(2, 1, 4)]));
+        assert_eq!(lookup(&table, "kelias"), None);
+        assert_eq!(lookup(&encode(&[]).unwrap(), "žodis"), None);
+    }
+
+    #[test]
+    fn bad_tables_are_rejected() {
+        let mut table = encode(&[(String::from("namas"), vec![option(2, 1, 1)])]).unwrap();
+        assert_eq!(lookup(&table[..table.len() - 1], "namas"), None);
+        table[4] = 2;
+        assert_eq!(word_count(&table), None);
+        assert_eq!(lookup(b"LTS", "namas"), None);
+    }
+
+    #[test]
+    fn words_that_overflow_their_entry_are_rejected() {
+        let options = vec![option(2, 1, 1); 256];
+        assert_eq!(
+            encode(&[(String::from("namas"), options)]),
+            Err(TooLarge {
+                word: String::from("namas"),
+                what: "options"
+            })
+        );
+        let split_options = [
+            (String::from("namas"), vec![option(2, 1, 1); 200]),
+            (String::from("namas"), vec![option(2, 1, 1); 56]),
+        ];
+        assert!(encode(&split_options).is_err());
+        let long_word = "a".repeat(usize::from(u16::MAX) + 1);
+        assert_eq!(
+            encode(&[(long_word.clone(), vec![option(2, 1, 1)])]),
+            Err(TooLarge {
+                word: long_word,
+                what: "bytes"
+            })
+        );
+        assert!(encode(&[(String::from("namas"), vec![option(2, 1, 1); 255])]).is_ok());
+    }
+}
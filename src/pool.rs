@@ -0,0 +1,136 @@
+use std::sync::{Arc, Mutex, PoisonError};
+
+use tokio::sync::mpsc;
+use tokio_stream::{wrappers::ReceiverStream, Stream};
+
+use crate::{accentuate_with, Case, PhonologyEngine, PhonologyError, StressResolver};
+
+/// A handle for accentuating words from async code without blocking the runtime.
+///
+/// Every call runs on tokio's blocking thread pool with [`tokio::task::spawn_blocking`], so the
+/// Python call (and waiting for the GIL) happens off the runtime's worker threads. Calls share one
+/// resolver, [`PhonologyEngine`] by default, and take turns using it, so only one word is looked
+/// up at a time; the GIL would serialize them anyway. Cloning the handle is cheap and the clones
+/// share the same resolver, so it can be kept in an axum state or passed to spawned tasks.
+///
+/// The methods have to be called from inside a tokio runtime.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::{AsyncEngine, Case};
+///
+/// # async fn run() {
+/// let engine = AsyncEngine::new();
+/// let accented = engine.accentuate("žodį", Case::Accusative).await.unwrap();
+/// assert_eq!(accented, "žõdį");
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct AsyncEngine<R = PhonologyEngine> {
+    resolver: Arc<Mutex<R>>,
+}
+
+impl AsyncEngine {
+    /// A handle that looks words up with `phonology_engine`.
+    pub fn new() -> Self {
+        Self::with_resolver(PhonologyEngine)
+    }
+}
+
+impl Default for AsyncEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R> Clone for AsyncEngine<R> {
+    fn clone(&self) -> Self {
+        Self {
+            resolver: Arc::clone(&self.resolver),
+        }
+    }
+}
+
+impl<R: StressResolver + Send + 'static> AsyncEngine<R> {
+    /// A handle that looks words up with `resolver` instead of the engine.
+    pub fn with_resolver(resolver: R) -> Self {
+        Self {
+            resolver: Arc::new(Mutex::new(resolver)),
+        }
+    }
+
+    /// Returns `word` with its accent marks in `case`, like [`crate::get_accentuation`] with the
+    /// case and no number.
+    pub async fn accentuate(&self, word: &str, case: Case) -> Result<String, PhonologyError> {
+        let resolver = Arc::clone(&self.resolver);
+        let owned = word.to_string();
+        tokio::task::spawn_blocking(move || {
+            // a resolver that panicked during an earlier call has no state left half-updated
+            let resolver = resolver.lock().unwrap_or_else(PoisonError::into_inner);
+            accentuate_with(
+                &*resolver,
+                &owned,
+                Some(case.lithuanian_name()),
+                None,
+                &Default::default(),
+            )
+            .map(|stressed| stressed.accented)
+        })
+        .await
+        .map_err(|e| PhonologyError::Engine {
+            word: word.to_string(),
+            message: e.to_string(),
+        })?
+    }
+
+    /// Accentuates every word in `case` like [`AsyncEngine::accentuate`], and yields each word and
+    /// its result as a [`Stream`] as soon as it is done, in the order of `words`, so that a large
+    /// batch can be passed on as it comes in rather than all at once at the end.
+    ///
+    /// The words are accentuated by a spawned task, which keeps at most
+    /// [`AsyncEngine::STREAM_BUFFER`] results waiting to be polled and stops once the stream is
+    /// dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use lithuanian_phonology::{AsyncEngine, Case};
+    /// use tokio_stream::StreamExt;
+    ///
+    /// # async fn run() {
+    /// let engine = AsyncEngine::new();
+    /// let mut results = engine.accentuate_stream(["žodį", "namą"], Case::Accusative);
+    /// while let Some((word, accented)) = results.next().await {
+    ///     println!("{word}: {}", accented.unwrap_or_default());
+    /// }
+    /// # }
+    /// ```
+    pub fn accentuate_stream<I>(
+        &self,
+        words: I,
+        case: Case,
+    ) -> impl Stream<Item = (String, Result<String, PhonologyError>)> + 'static
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let words: Vec<String> = words.into_iter().map(Into::into).collect();
+        let (sender, receiver) = mpsc::channel(Self::STREAM_BUFFER);
+        let engine = self.clone();
+        tokio::spawn(async move {
+            for word in words {
+                let result = engine.accentuate(&word, case).await;
+                if sender.send((word, result)).await.is_err() {
+                    break;
+                }
+            }
+        });
+        ReceiverStream::new(receiver)
+    }
+}
+
+impl<R> AsyncEngine<R> {
+    /// How many results [`AsyncEngine::accentuate_stream`] accentuates ahead of the receiver.
+    pub const STREAM_BUFFER: usize = 32;
+}
@@ -0,0 +1,125 @@
+use crate::{
+    select_option, stressed_word, syllables, AccentuationOptions, PhonologyEngine, PhonologyError,
+    StressResolver, StressedWord,
+};
+
+/// Negation prefixes, longest first: `nebe-` ("no longer") and `ne-`.
+const NEGATION_PREFIXES: [&str; 2] = ["nebe", "ne"];
+
+/// Like [`crate::accentuate`], but also handles negated forms like `nežinau` and `nebebuvo` that
+/// the engine has no options for.
+///
+/// When the engine knows the whole form, its options are used as they are, since its indices are
+/// already counted from the start of the whole form. Otherwise the `nebe-` or `ne-` is taken off,
+/// the rest is accentuated, and the prefix is put back in front with the stress where it was in
+/// the stem, just moved over by the length of the prefix: `žinaũ` becomes `nežinaũ` and `bùvo`
+/// becomes `nebùvo`. That's where the stress goes in the standard language for nearly every verb.
+/// The few forms where `ne-` takes the stress itself, like `nèsą` or the contracted `nėra`, are
+/// in the engine's vocabulary, so they don't need the prefix taken off.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::{accentuate_negated, AccentuationOptions};
+///
+/// let stressed = accentuate_negated("nežinau", None, None, &AccentuationOptions::default());
+/// assert_eq!(stressed.unwrap().accented, "nežinaũ");
+/// ```
+pub fn accentuate_negated(
+    word: &str,
+    case: Option<&str>,
+    number: Option<&str>,
+    options: &AccentuationOptions,
+) -> Result<StressedWord, PhonologyError> {
+    accentuate_negated_with(&PhonologyEngine, word, case, number, options)
+}
+
+/// Like [`accentuate_negated`], but gets the stress options from `resolver` instead of the engine.
+pub fn accentuate_negated_with(
+    resolver: &impl StressResolver,
+    word: &str,
+    case: Option<&str>,
+    number: Option<&str>,
+    options: &AccentuationOptions,
+) -> Result<StressedWord, PhonologyError> {
+    let mut stress_options = resolver.stress_options(word)?;
+    if stress_options.is_empty() {
+        for prefix in NEGATION_PREFIXES {
+            // The prefixes are ASCII, so comparing this way never has to lowercase the rest of
+            // the word, which could change its length.
+            let is_negated = word
+                .get(..prefix.len())
+                .is_some_and(|start| start.eq_ignore_ascii_case(prefix));
+            if !is_negated {
+                continue;
+            }
+            let stem = &word[prefix.len()..];
+            if syllables::nuclei(stem).is_empty() {
+                continue;
+            }
+            stress_options = resolver.stress_options(stem)?;
+            if !stress_options.is_empty() {
+                for option in &mut stress_options {
+                    option.stressed_letter_index += prefix.chars().count();
+                }
+                break;
+            }
+        }
+    }
+    let (option, fell_back) = select_option(word, &stress_options, case, number, options)?;
+    stressed_word(word, option.clone(), fell_back, options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StressOption;
+    use unicode_normalization::UnicodeNormalization;
+
+    fn resolver(word: &str) -> Result<Vec<StressOption>, PhonologyError> {
+        let option = |stress_type, stressed_letter_index| StressOption {
+            stress_type,
+            stressed_letter_index,
+            ..Default::default()
+        };
+        match word {
+            "žinau" => Ok(vec![option(2, 4)]),
+            "buvo" => Ok(vec![option(0, 1)]),
+            "nėra" => Ok(vec![option(2, 1)]),
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    fn negated(word: &str) -> Result<String, PhonologyError> {
+        accentuate_negated_with(&resolver, word, None, None, &Default::default())
+            .map(|stressed| stressed.accented.nfc().collect())
+    }
+
+    #[test]
+    fn stem_stress_moves_over_by_prefix() {
+        assert_eq!(negated("nežinau").unwrap(), "nežinaũ");
+        assert_eq!(negated("Nežinau").unwrap(), "Nežinaũ");
+        assert_eq!(
+            negated("nebuvo").unwrap(),
+            "nebùvo".nfc().collect::<String>()
+        );
+        assert_eq!(
+            negated("nebebuvo").unwrap(),
+            "nebebùvo".nfc().collect::<String>()
+        );
+    }
+
+    #[test]
+    fn whole_form_from_engine_is_kept() {
+        assert_eq!(negated("nėra").unwrap(), "nė̃ra");
+    }
+
+    #[test]
+    fn unknown_forms_are_errors() {
+        assert!(negated("nevisai").is_err());
+        assert!(negated("ne").is_err());
+        // The Kelvin sign lowercases to a shorter `k`.
+        assert!(negated("ne\u{212A}a").is_err());
+        assert!(negated("\u{212A}ne").is_err());
+    }
+}
@@ -0,0 +1,164 @@
+use crate::{
+    select_option, stressed_grapheme, syllabify, syllable_starts, try_stress_with_option, Case,
+    PhonologyConfig, PhonologyEngine, PhonologyError, StressResolver,
+};
+
+/// A word accented and split into syllables, as returned by [`pronunciation`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pronunciation {
+    /// The word with its accent marks, like `galvõs`.
+    pub accented: String,
+    /// The syllables of the accented word, like `["gal", "võs"]`.
+    pub syllables: Vec<String>,
+    /// Which of the syllables (counting from 0) is stressed.
+    pub stressed_syllable: usize,
+}
+
+impl Pronunciation {
+    /// How many syllables from the end of the word the stress is: 0 for the last syllable (the
+    /// ultima), 1 for the penult, 2 for the antepenult and so on.
+    pub fn stress_from_end(&self) -> usize {
+        self.syllables
+            .len()
+            .saturating_sub(self.stressed_syllable + 1)
+    }
+}
+
+/// Takes a word and returns how it is pronounced in a case: its accented form, its syllables (see
+/// [`syllabify`]) and which of them is stressed, all from one lookup of the stress options.
+///
+/// The stress option is chosen as in [`crate::get_accentuation`], so a word that has options, but
+/// none in the case, is a [`PhonologyError::CaseNotFound`] error.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::{pronunciation, Case};
+///
+/// let galvos = pronunciation("galvos", Case::Genitive).unwrap();
+/// assert_eq!(galvos.accented, "galvõs");
+/// assert_eq!(galvos.syllables, ["gal", "võs"]);
+/// assert_eq!(galvos.stressed_syllable, 1);
+/// ```
+pub fn pronunciation(word: &str, case: Case) -> Result<Pronunciation, PhonologyError> {
+    pronunciation_with(&PhonologyEngine, word, case)
+}
+
+/// Like [`pronunciation`], but gets the stress options from `resolver` instead of the engine.
+pub fn pronunciation_with(
+    resolver: &impl StressResolver,
+    word: &str,
+    case: Case,
+) -> Result<Pronunciation, PhonologyError> {
+    let options = resolver.stress_options(word)?;
+    let (option, _) = select_option(
+        word,
+        &options,
+        Some(case.lithuanian_name()),
+        None,
+        &Default::default(),
+    )?;
+    let accented = try_stress_with_option(word, option)?;
+
+    // the last syllable that starts at or before the grapheme the mark went on
+    let stressed_grapheme = stressed_grapheme(word, option);
+    let stressed_syllable = syllable_starts(word, PhonologyConfig::standard())
+        .iter()
+        .rposition(|&start| start <= stressed_grapheme)
+        .unwrap_or(0);
+
+    Ok(Pronunciation {
+        syllables: syllabify(&accented),
+        accented,
+        stressed_syllable,
+    })
+}
+
+/// Takes a word and returns how many syllables from its end the stress falls in a case, 0 being
+/// the last one, which is how accent paradigms usually describe where a form is stressed. See
+/// [`Pronunciation::stress_from_end`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::{stress_from_end, Case};
+///
+/// assert_eq!(stress_from_end("galvos", Case::Genitive).unwrap(), 0);
+/// assert_eq!(stress_from_end("galvos", Case::Nominative).unwrap(), 1);
+/// ```
+pub fn stress_from_end(word: &str, case: Case) -> Result<usize, PhonologyError> {
+    stress_from_end_with(&PhonologyEngine, word, case)
+}
+
+/// Like [`stress_from_end`], but gets the stress options from `resolver` instead of the engine.
+pub fn stress_from_end_with(
+    resolver: &impl StressResolver,
+    word: &str,
+    case: Case,
+) -> Result<usize, PhonologyError> {
+    pronunciation_with(resolver, word, case).map(|pronunciation| pronunciation.stress_from_end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StressOption;
+
+    fn option(case: Case, stress_type: u8, stressed_letter_index: usize) -> StressOption {
+        StressOption {
+            stress_type,
+            stressed_letter_index,
+            grammatical_case: Some(case.lithuanian_name().to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn resolver(word: &str) -> Result<Vec<StressOption>, PhonologyError> {
+        Ok(match word {
+            "galvos" => vec![option(Case::Genitive, 2, 4), option(Case::Nominative, 1, 1)],
+            // the engine puts the stress on the softening i
+            "kelio" => vec![option(Case::Genitive, 2, 3)],
+            "mokytojas" => vec![option(Case::Nominative, 1, 3)],
+            _ => Vec::new(),
+        })
+    }
+
+    #[test]
+    fn syllables_and_stress() {
+        let galvos = pronunciation_with(&resolver, "galvos", Case::Nominative).unwrap();
+        assert_eq!(
+            galvos,
+            Pronunciation {
+                accented: String::from("gálvos"),
+                syllables: vec![String::from("gál"), String::from("vos")],
+                stressed_syllable: 0,
+            }
+        );
+        let galvos = pronunciation_with(&resolver, "galvos", Case::Genitive).unwrap();
+        assert_eq!(
+            (galvos.syllables[1].as_str(), galvos.stressed_syllable),
+            ("võs", 1)
+        );
+
+        let kelio = pronunciation_with(&resolver, "kelio", Case::Genitive).unwrap();
+        assert_eq!(kelio.syllables, ["ke", "liõ"]);
+        assert_eq!(kelio.stressed_syllable, 1);
+
+        assert!(matches!(
+            pronunciation_with(&resolver, "galvos", Case::Dative),
+            Err(PhonologyError::CaseNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn syllables_from_the_end() {
+        let from_end = |word, case| stress_from_end_with(&resolver, word, case).unwrap();
+        assert_eq!(from_end("galvos", Case::Genitive), 0);
+        assert_eq!(from_end("galvos", Case::Nominative), 1);
+        assert_eq!(from_end("kelio", Case::Genitive), 0);
+        // mo-ký-to-jas
+        assert_eq!(from_end("mokytojas", Case::Nominative), 2);
+        assert!(stress_from_end_with(&resolver, "galvos", Case::Dative).is_err());
+    }
+}
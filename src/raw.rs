@@ -0,0 +1,231 @@
+use std::collections::HashMap;
+
+use pyo3::{PyAny, PyErr, PyObject, Python};
+
+use crate::{detail_text, engine, stress_option_from_decoded, PhonologyError, StressOption};
+
+/// One item yielded by the engine's `process` generator.
+///
+/// `process` takes a whole text and yields one item at a time, each a list of lists of words. The
+/// engine doesn't document what the two levels of lists stand for; in practice an item is a
+/// sentence-sized chunk of the text and each inner list a phrase within it, but nothing in the
+/// crate relies on that. A single word comes back as one item with one list holding just that
+/// word, which is the `[0][0]` every other function in the crate reads.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RawUnit {
+    /// The inner lists of the item, in the engine's order.
+    pub groups: Vec<Vec<RawWord>>,
+}
+
+/// One word in a [`RawUnit`]: a dictionary in the engine's output.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RawWord {
+    /// The `word` field, the word as the engine read it from the text, if there is one.
+    pub word: Option<String>,
+    /// The `decoded_options` inside the `stress_options` field, read like
+    /// [`crate::get_stress_options`] does. Empty if the word has no `stress_options`, as is the
+    /// case for punctuation.
+    pub stress_options: Vec<StressOption>,
+    /// Every other field (`transcription` among them, in engines that give one) that is text, a
+    /// bool or a number, as text. Fields that are lists or dictionaries are left out.
+    pub fields: HashMap<String, String>,
+}
+
+/// Runs a text through the engine and returns everything its `process` generator yields, with
+/// every item and inner list rather than just the first word. This is the escape hatch for
+/// anything the other functions don't cover, such as reading whole sentences at once or fields of
+/// the engine's output the crate doesn't otherwise use.
+///
+/// The stress options of each word are checked against its `word` field, or against all of `text`
+/// when the engine doesn't give one. An item that isn't a list of lists of dictionaries is a
+/// [`PhonologyError::Engine`] error, and `stress_options` that can't be read are a
+/// [`PhonologyError::InvalidField`] error.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::process_raw;
+///
+/// for unit in process_raw("Labas rytas. Kaip sekasi?").unwrap() {
+///     for word in unit.groups.iter().flatten() {
+///         println!("{:?}: {} options", word.word, word.stress_options.len());
+///     }
+/// }
+/// ```
+pub fn process_raw(text: &str) -> Result<Vec<RawUnit>, PhonologyError> {
+    Python::with_gil(|py| process_raw_py(py, text))
+}
+
+/// Like [`process_raw`], but uses a GIL that is already held.
+pub fn process_raw_py(py: Python<'_>, text: &str) -> Result<Vec<RawUnit>, PhonologyError> {
+    let generator = engine(py)
+        .and_then(|engine| engine.getattr("process")?.call1((text,)))
+        .map_err(|e| engine_error(text, e))?;
+    raw_units(py, text, generator)
+}
+
+/// Returns how the engine splits a text into the units it stresses, in order, without stressing
+/// anything: the `word` field of every word [`process_raw`] gives, across all of its items and
+/// inner lists. This is for lining up another tokenizer with the engine's.
+///
+/// The engine's units are mostly the orthographic words of the text, but not always. Punctuation
+/// comes back as units of its own, and whitespace doesn't come back at all. Some long or
+/// hyphenated compounds are split into several units, each stressed on its own (see
+/// [`crate::stress_units`]), and the engine may normalize what it reads, so a unit isn't always a
+/// substring of `text`. Words the engine gives without a `word` field are left out.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::segment;
+///
+/// assert_eq!(segment("Labas rytas!").unwrap(), ["Labas", "rytas", "!"]);
+/// ```
+pub fn segment(text: &str) -> Result<Vec<String>, PhonologyError> {
+    Ok(segments(&process_raw(text)?))
+}
+
+fn segments(units: &[RawUnit]) -> Vec<String> {
+    units
+        .iter()
+        .flat_map(|unit| unit.groups.iter().flatten())
+        .filter_map(|word| word.word.clone())
+        .collect()
+}
+
+fn engine_error(text: &str, error: PyErr) -> PhonologyError {
+    PhonologyError::Engine {
+        word: text.to_string(),
+        message: error.to_string(),
+    }
+}
+
+// Reads anything that can be iterated like the `process` generator.
+fn raw_units(py: Python<'_>, text: &str, items: &PyAny) -> Result<Vec<RawUnit>, PhonologyError> {
+    let error = |e| engine_error(text, e);
+    let mut units = Vec::new();
+    for item in items.iter().map_err(error)? {
+        let mut groups = Vec::new();
+        for group in item.map_err(error)?.iter().map_err(error)? {
+            let mut words = Vec::new();
+            for word in group.map_err(error)?.iter().map_err(error)? {
+                words.push(raw_word(py, text, word.map_err(error)?)?);
+            }
+            groups.push(words);
+        }
+        units.push(RawUnit { groups });
+    }
+    Ok(units)
+}
+
+fn raw_word(py: Python<'_>, text: &str, word: &PyAny) -> Result<RawWord, PhonologyError> {
+    let fields: HashMap<String, PyObject> = word.extract().map_err(|e| engine_error(text, e))?;
+    let name = fields
+        .get("word")
+        .and_then(|word| word.extract::<String>(py).ok());
+    let checked_against = name.as_deref().unwrap_or(text);
+
+    let decoded: Vec<HashMap<String, PyObject>> = match fields.get("stress_options") {
+        Some(options) => options
+            .as_ref(py)
+            .get_item("decoded_options")
+            .and_then(|decoded| decoded.extract())
+            .map_err(|e| PhonologyError::InvalidField {
+                word: checked_against.to_string(),
+                field: "stress_options",
+                message: e.to_string(),
+            })?,
+        None => Vec::new(),
+    };
+    let stress_options = decoded
+        .into_iter()
+        .map(|option| stress_option_from_decoded(py, checked_against, option))
+        .collect::<Result<_, _>>()?;
+
+    Ok(RawWord {
+        stress_options,
+        fields: fields
+            .iter()
+            .filter(|(k, _)| !["word", "stress_options"].contains(&k.as_str()))
+            .filter_map(|(k, v)| Some((k.clone(), detail_text(py, v)?)))
+            .collect(),
+        word: name,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_every_item_and_group() {
+        Python::with_gil(|py| {
+            let output = py
+                .eval(
+                    r#"iter([
+                        [[{"word": "žodį", "transcription": "Z o: d i:", "stress_options":
+                            {"decoded_options": [{"stress_type": 2, "stressed_letter_index": 1,
+                                "grammatical_case": "Galininkas"}]}},
+                          {"word": "!", "letters": ["!"]}]],
+                        [[{"word": "gera", "stress_options": {"decoded_options": []}}], []],
+                    ])"#,
+                    None,
+                    None,
+                )
+                .unwrap();
+            let units = raw_units(py, "žodį! gera", output).unwrap();
+            assert_eq!(units.len(), 2);
+
+            let word = &units[0].groups[0][0];
+            assert_eq!(word.word.as_deref(), Some("žodį"));
+            assert_eq!(word.fields["transcription"], "Z o: d i:");
+            assert_eq!(word.stress_options[0].stressed_letter_index, 1);
+            assert_eq!(
+                word.stress_options[0].grammatical_case.as_deref(),
+                Some("Galininkas")
+            );
+
+            let punctuation = &units[0].groups[0][1];
+            assert!(punctuation.stress_options.is_empty());
+            assert!(punctuation.fields.is_empty());
+            assert_eq!(units[1].groups[1], []);
+        });
+    }
+
+    #[test]
+    fn segments_of_every_item() {
+        Python::with_gil(|py| {
+            let output = py
+                .eval(
+                    r#"iter([
+                        [[{"word": "juodai"}, {"word": "-"}, {"word": "baltas"}], [{"word": "!"}]],
+                        [[{"letters": []}, {"word": "gera"}]],
+                    ])"#,
+                    None,
+                    None,
+                )
+                .unwrap();
+            let units = raw_units(py, "juodai-baltas! gera", output).unwrap();
+            assert_eq!(segments(&units), ["juodai", "-", "baltas", "!", "gera"]);
+        });
+        assert_eq!(segments(&[]), Vec::<String>::new());
+    }
+
+    #[test]
+    fn unexpected_shapes_are_errors() {
+        Python::with_gil(|py| {
+            let output = py.eval("[[[1]]]", None, None).unwrap();
+            assert!(matches!(
+                raw_units(py, "žodį", output),
+                Err(PhonologyError::Engine { .. })
+            ));
+            let output = py
+                .eval(r#"[[[{"word": "žodį", "stress_options": 3}]]]"#, None, None)
+                .unwrap();
+            assert!(matches!(
+                raw_units(py, "žodį", output),
+                Err(PhonologyError::InvalidField { .. })
+            ));
+        });
+    }
+}
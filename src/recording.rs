@@ -0,0 +1,276 @@
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, Write},
+    sync::{Mutex, PoisonError},
+};
+
+use crate::{PhonologyEngine, PhonologyError, StressOption, StressResolver};
+
+/// A [`StressResolver`] that passes every word on to another resolver and remembers what it
+/// answered, so that the options from a run with the engine can be saved and used as a
+/// [`StressDictionary`] in tests that run without Python.
+///
+/// Each word is recorded once, with the options from the first time it was looked up. Errors are
+/// passed on without being recorded.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::fs::File;
+///
+/// use lithuanian_phonology::{accentuate_with, RecordingResolver, StressDictionary};
+///
+/// let recorder = RecordingResolver::new();
+/// accentuate_with(&recorder, "žodį", Some("Galininkas"), None, &Default::default()).unwrap();
+/// recorder.write_tsv(File::create("fixtures.tsv").unwrap()).unwrap();
+///
+/// // later, without Python
+/// let file = std::io::BufReader::new(File::open("fixtures.tsv").unwrap());
+/// let dictionary = StressDictionary::from_tsv(file).unwrap();
+/// let options = Default::default();
+/// let stressed = accentuate_with(&dictionary, "žodį", Some("Galininkas"), None, &options).unwrap();
+/// assert_eq!(stressed.accented, "žõdį");
+/// ```
+#[derive(Debug, Default)]
+pub struct RecordingResolver<R = PhonologyEngine> {
+    resolver: R,
+    recorded: Mutex<Vec<(String, Vec<StressOption>)>>,
+}
+
+impl RecordingResolver {
+    /// Records what the engine answers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<R: StressResolver> RecordingResolver<R> {
+    /// Records what `resolver` answers.
+    pub fn with_resolver(resolver: R) -> Self {
+        Self {
+            resolver,
+            recorded: Mutex::default(),
+        }
+    }
+
+    /// Every word looked up so far and its options, in the order they were first looked up.
+    pub fn recorded(&self) -> Vec<(String, Vec<StressOption>)> {
+        self.recorded
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Writes the recorded options as TSV, one option per line: the word, the stress type, the
+    /// stressed letter index, and the case and number if the option has them. This is the format of
+    /// `data/offline.tsv`, so the file can also be built into the crate's offline table (see
+    /// `lookup_offline`, with the `offline` feature). Genders and [`StressOption::details`] aren't
+    /// written, and neither are words without options.
+    pub fn write_tsv(&self, mut writer: impl Write) -> io::Result<()> {
+        writeln!(
+            writer,
+            "# word\tstress type\tstressed letter index\tcase\tnumber"
+        )?;
+        for (word, options) in self.recorded() {
+            for option in options {
+                write!(
+                    writer,
+                    "{word}\t{}\t{}",
+                    option.stress_type, option.stressed_letter_index
+                )?;
+                match (&option.grammatical_case, &option.number) {
+                    (Some(case), Some(number)) => write!(writer, "\t{case}\t{number}")?,
+                    (Some(case), None) => write!(writer, "\t{case}")?,
+                    (None, Some(number)) => write!(writer, "\t\t{number}")?,
+                    (None, None) => {}
+                }
+                writeln!(writer)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the recorded options as a JSON array of `{"word": ..., "options": [...]}` objects,
+    /// with every field of each option.
+    #[cfg(feature = "serde")]
+    pub fn write_json(&self, writer: impl Write) -> io::Result<()> {
+        let entries: Vec<_> = self
+            .recorded()
+            .into_iter()
+            .map(|(word, options)| serde_json::json!({ "word": word, "options": options }))
+            .collect();
+        serde_json::to_writer(writer, &entries).map_err(io::Error::from)
+    }
+}
+
+impl<R: StressResolver> StressResolver for RecordingResolver<R> {
+    fn stress_options(&self, word: &str) -> Result<Vec<StressOption>, PhonologyError> {
+        let options = self.resolver.stress_options(word)?;
+        let mut recorded = self.recorded.lock().unwrap_or_else(PoisonError::into_inner);
+        if !recorded.iter().any(|(recorded, _)| recorded == word) {
+            recorded.push((word.to_string(), options.clone()));
+        }
+        Ok(options)
+    }
+}
+
+/// A [`StressResolver`] that answers from stress options loaded from a file, such as one written
+/// by a [`RecordingResolver`]. Words that aren't in it have no options.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StressDictionary {
+    words: HashMap<String, Vec<StressOption>>,
+}
+
+impl StressDictionary {
+    /// Reads options in the TSV format of [`RecordingResolver::write_tsv`]. Blank lines and lines
+    /// starting with `#` are skipped, and a line that can't be read is an
+    /// [`io::ErrorKind::InvalidData`] error naming it.
+    pub fn from_tsv(reader: impl BufRead) -> io::Result<Self> {
+        let mut words: HashMap<String, Vec<StressOption>> = HashMap::new();
+        for (number, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = || {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {} isn't a stress option: '{line}'", number + 1),
+                )
+            };
+            let mut fields = line.split('\t');
+            let word = fields.next().ok_or_else(invalid)?;
+            let stress_type = fields.next().and_then(|f| f.parse().ok());
+            let index = fields.next().and_then(|f| f.parse().ok());
+            let (Some(stress_type), Some(stressed_letter_index)) = (stress_type, index) else {
+                return Err(invalid());
+            };
+            let text = |field: Option<&str>| field.filter(|f| !f.is_empty()).map(str::to_string);
+            let option = StressOption {
+                stress_type,
+                stressed_letter_index,
+                grammatical_case: text(fields.next()),
+                number: text(fields.next()),
+                ..Default::default()
+            };
+            words.entry(word.to_string()).or_default().push(option);
+        }
+        Ok(Self { words })
+    }
+
+    /// Reads options in the JSON format of [`RecordingResolver::write_json`].
+    #[cfg(feature = "serde")]
+    pub fn from_json(reader: impl io::Read) -> io::Result<Self> {
+        #[derive(serde::Deserialize)]
+        struct Entry {
+            word: String,
+            options: Vec<StressOption>,
+        }
+        let entries: Vec<Entry> = serde_json::from_reader(reader).map_err(io::Error::from)?;
+        let words = entries
+            .into_iter()
+            .map(|entry| (entry.word, entry.options))
+            .collect();
+        Ok(Self { words })
+    }
+}
+
+impl StressResolver for StressDictionary {
+    fn stress_options(&self, word: &str) -> Result<Vec<StressOption>, PhonologyError> {
+        Ok(self.words.get(word).cloned().unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    fn resolver(word: &str) -> Result<Vec<StressOption>, PhonologyError> {
+        Ok(match word {
+            "žodį" => vec![StressOption {
+                stress_type: 2,
+                stressed_letter_index: 1,
+                grammatical_case: Some(String::from("Galininkas")),
+                number: Some(String::from("vienaskaita")),
+                gender: Some(String::from("vyriškoji")),
+                ..Default::default()
+            }],
+            "gerai" => vec![StressOption {
+                stress_type: 2,
+                stressed_letter_index: 4,
+                ..Default::default()
+            }],
+            _ => Vec::new(),
+        })
+    }
+
+    fn recorder() -> RecordingResolver<impl StressResolver> {
+        let recorder = RecordingResolver::with_resolver(resolver);
+        for word in ["žodį", "gerai", "žodį", "nežinomas"] {
+            recorder.stress_options(word).unwrap();
+        }
+        recorder
+    }
+
+    #[test]
+    fn records_each_word_once() {
+        let words: Vec<String> = recorder().recorded().into_iter().map(|(w, _)| w).collect();
+        assert_eq!(words, ["žodį", "gerai", "nežinomas"]);
+
+        let calls = Cell::new(0);
+        let failing = |word: &str| {
+            calls.set(calls.get() + 1);
+            Err(PhonologyError::NoVocative {
+                word: word.to_string(),
+            })
+        };
+        let recorder = RecordingResolver::with_resolver(failing);
+        assert!(recorder.stress_options("žodį").is_err());
+        assert_eq!((calls.get(), recorder.recorded()), (1, Vec::new()));
+    }
+
+    #[test]
+    fn tsv_round_trip() {
+        let mut tsv = Vec::new();
+        recorder().write_tsv(&mut tsv).unwrap();
+        let tsv = String::from_utf8(tsv).unwrap();
+        assert_eq!(
+            tsv,
+            "# word\tstress type\tstressed letter index\tcase\tnumber\n\
+             žodį\t2\t1\tGalininkas\tvienaskaita\n\
+             gerai\t2\t4\n"
+        );
+
+        let dictionary = StressDictionary::from_tsv(tsv.as_bytes()).unwrap();
+        for word in ["žodį", "gerai", "nežinomas"] {
+            let mut expected = resolver(word).unwrap();
+            for option in &mut expected {
+                option.gender = None;
+            }
+            assert_eq!(dictionary.stress_options(word).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn invalid_tsv_lines() {
+        let error =
+            StressDictionary::from_tsv("žodį\t2\t1\nžodis\tdu\t1\n".as_bytes()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(error.to_string().contains("line 2"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json_round_trip() {
+        let mut json = Vec::new();
+        recorder().write_json(&mut json).unwrap();
+        let dictionary = StressDictionary::from_json(json.as_slice()).unwrap();
+        assert_eq!(
+            dictionary.stress_options("žodį").unwrap(),
+            resolver("žodį").unwrap()
+        );
+        assert_eq!(dictionary.stress_options("nežinomas").unwrap(), Vec::new());
+    }
+}
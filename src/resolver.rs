@@ -0,0 +1,221 @@
+use pyo3::{exceptions::PyModuleNotFoundError, PyErr, Python};
+
+use crate::{engine, get_stress_options_py, PhonologyError, StressOption};
+
+/// Something that can come up with the stress options for a word. [`PhonologyEngine`] asks
+/// `phonology_engine` for them, and any closure that takes a word and returns the options works as
+/// well, so the parts of the crate that take a resolver can be tried out without Python.
+///
+/// # Examples
+///
+/// ```
+/// use lithuanian_phonology::{PhonologyError, StressOption, StressResolver};
+///
+/// let resolver = |_: &str| -> Result<Vec<StressOption>, PhonologyError> {
+///     Ok(vec![StressOption {
+///         stress_type: 2,
+///         stressed_letter_index: 1,
+///         grammatical_case: Some(String::from("Galininkas")),
+///         ..Default::default()
+///     }])
+/// };
+/// assert_eq!(resolver.stress_options("žodį").unwrap()[0].stressed_letter_index, 1);
+/// ```
+pub trait StressResolver {
+    /// All the stress options for a word, like [`crate::get_stress_options`].
+    fn stress_options(&self, word: &str) -> Result<Vec<StressOption>, PhonologyError>;
+}
+
+/// The resolver that asks `phonology_engine`, acquiring the GIL for each word.
+///
+/// The engine is started the first time any function needs it and then kept for the rest of the
+/// process, so a `PhonologyEngine` is free to create and copy. Creating it with `PhonologyEngine`
+/// or [`Default`] doesn't check anything, so a missing engine only shows up as an
+/// [`PhonologyError::Engine`] error on the first word; [`PhonologyEngine::new`] starts the engine
+/// right away instead, and says clearly what is wrong if it can't.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::{accentuate_with, PhonologyEngine, PhonologyError};
+///
+/// let engine = match PhonologyEngine::new() {
+///     Ok(engine) => engine,
+///     Err(PhonologyError::ModuleNotFound { message }) => {
+///         panic!("install phonology_engine first: {message}")
+///     }
+///     Err(error) => panic!("{error}"),
+/// };
+/// for word in ["žodis", "namas", "kelias"] {
+///     let stressed = accentuate_with(&engine, word, None, None, &Default::default()).unwrap();
+///     println!("{}", stressed.accented);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PhonologyEngine;
+
+impl PhonologyEngine {
+    /// Starts the engine, so that setup problems are found before the first word rather than on
+    /// it. The errors are:
+    ///
+    /// - [`PhonologyError::ModuleNotFound`] if Python can't find the `phonology_engine` module,
+    ///   which usually means it isn't installed for the interpreter pyo3 linked to
+    /// - [`PhonologyError::EngineSetup`] if the module is there but creating its engine failed, for
+    ///   example because its dictionary files are missing
+    ///
+    /// The interpreter itself is started by pyo3 when the GIL is first acquired; if that fails pyo3
+    /// panics, since there is no Python to report an error from.
+    pub fn new() -> Result<Self, PhonologyError> {
+        Python::with_gil(|py| {
+            engine(py)
+                .map(|_| PhonologyEngine)
+                .map_err(|e| setup_error(py, e))
+        })
+    }
+}
+
+/// What [`check_environment`] found: the Python that pyo3 is linked to, and where it found
+/// `phonology_engine`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvInfo {
+    /// The interpreter's `sys.version`, like `3.11.4 (main, ...) [GCC 12.2.0]`.
+    pub python_version: String,
+    /// The file `phonology_engine` was imported from, or `None` for a module without one.
+    pub module_path: Option<String>,
+}
+
+/// Checks everything the engine needs, one step at a time, so that a broken setup can be told
+/// apart from a broken word. The steps are starting the interpreter, importing
+/// `phonology_engine` and creating its engine, and the first of them that fails is the error:
+///
+/// - [`PhonologyError::ModuleNotFound`] if the import fails. The message says which Python was
+///   searched and where, since the module is often installed, but for a different interpreter
+///   than the one pyo3 linked to.
+/// - [`PhonologyError::EngineSetup`] if the module is there but has no engine or creating it
+///   failed.
+///
+/// As with [`PhonologyEngine::new`], pyo3 panics if the interpreter itself can't be started.
+/// Once this succeeds the engine is running, so it also does what [`PhonologyEngine::new`] does.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::check_environment;
+///
+/// match check_environment() {
+///     Ok(info) => println!("using Python {}", info.python_version),
+///     Err(error) => eprintln!("{error}"),
+/// }
+/// ```
+pub fn check_environment() -> Result<EnvInfo, PhonologyError> {
+    Python::with_gil(|py| {
+        let python_version = py.version().to_string();
+        let module = py.import("phonology_engine").map_err(|e| {
+            let error = setup_error(py, e);
+            match error {
+                PhonologyError::ModuleNotFound { message } => PhonologyError::ModuleNotFound {
+                    message: format!("{message} ({})", interpreter_description(py)),
+                },
+                error => error,
+            }
+        })?;
+        let module_path = module
+            .getattr("__file__")
+            .and_then(|path| path.extract::<Option<String>>())
+            .ok()
+            .flatten();
+        engine(py).map_err(|e| setup_error(py, e))?;
+        Ok(EnvInfo {
+            python_version,
+            module_path,
+        })
+    })
+}
+
+// Which interpreter looked for a module, and where, like `searched by /usr/bin/python3 (Python
+// 3.11.4) in /usr/lib/python3.11, ...`.
+fn interpreter_description(py: Python<'_>) -> String {
+    let version = py.version_info();
+    let sys = py.import("sys");
+    let attribute =
+        |name: &str| -> Option<String> { sys.as_ref().ok()?.getattr(name).ok()?.extract().ok() };
+    let executable = attribute("executable").unwrap_or_else(|| String::from("an unknown Python"));
+    let paths: Vec<String> = sys
+        .ok()
+        .and_then(|sys| sys.getattr("path").ok()?.extract().ok())
+        .unwrap_or_default();
+    format!(
+        "searched by {executable} (Python {}.{}.{}) in {}",
+        version.major,
+        version.minor,
+        version.patch,
+        if paths.is_empty() {
+            String::from("no directories")
+        } else {
+            paths.join(", ")
+        }
+    )
+}
+
+fn setup_error(py: Python<'_>, error: PyErr) -> PhonologyError {
+    let message = error.to_string();
+    if error.is_instance_of::<PyModuleNotFoundError>(py) {
+        PhonologyError::ModuleNotFound { message }
+    } else {
+        PhonologyError::EngineSetup { message }
+    }
+}
+
+impl StressResolver for PhonologyEngine {
+    fn stress_options(&self, word: &str) -> Result<Vec<StressOption>, PhonologyError> {
+        Python::with_gil(|py| get_stress_options_py(py, word))
+    }
+}
+
+impl<F> StressResolver for F
+where
+    F: Fn(&str) -> Result<Vec<StressOption>, PhonologyError>,
+{
+    fn stress_options(&self, word: &str) -> Result<Vec<StressOption>, PhonologyError> {
+        self(word)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pyo3::exceptions::PyRuntimeError;
+
+    #[test]
+    fn setup_errors_say_what_is_missing() {
+        Python::with_gil(|py| {
+            let missing = PyModuleNotFoundError::new_err("No module named 'phonology_engine'");
+            assert_eq!(
+                setup_error(py, missing),
+                PhonologyError::ModuleNotFound {
+                    message: String::from(
+                        "ModuleNotFoundError: No module named 'phonology_engine'"
+                    )
+                }
+            );
+            let failed = PyRuntimeError::new_err("dictionary not found");
+            assert!(matches!(
+                setup_error(py, failed),
+                PhonologyError::EngineSetup { .. }
+            ));
+        });
+    }
+
+    #[test]
+    fn environment_check() {
+        // whether the engine is installed depends on the machine the tests run on
+        match check_environment() {
+            Ok(info) => assert!(!info.python_version.is_empty()),
+            Err(PhonologyError::ModuleNotFound { message }) => {
+                assert!(message.contains("phonology_engine"), "{message}");
+                assert!(message.contains("searched by"), "{message}");
+            }
+            Err(error) => panic!("{error}"),
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use crate::{
+    select_option, text, try_stress_with_option, Case, PhonologyEngine, PhonologyError,
+    StressResolver,
+};
+
+/// How [`to_ruby_html`] lays out the two forms of a word.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RubyOptions {
+    /// Put the accented form in the base text and the plain word in the `<rt>` above it, instead
+    /// of the other way round (the default).
+    pub accented_base: bool,
+}
+
+/// Takes a word and returns it as an HTML `<ruby>` element with its accented form for the case
+/// given, like `<ruby>gera<rt>gerà</rt></ruby>`, so that learner materials can show the stress
+/// above the word as it is usually written.
+///
+/// Both forms are HTML-escaped. A word the engine has no stress options for at all, like a number
+/// or a foreign name, is returned as escaped plain text without the `<ruby>`, while one that only
+/// has options in other cases is a [`PhonologyError::CaseNotFound`] error, as with
+/// [`crate::get_accentuation`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::{to_ruby_html, Case, RubyOptions};
+///
+/// let html = to_ruby_html("žodį", Case::Accusative, &RubyOptions::default()).unwrap();
+/// assert_eq!(html, "<ruby>žodį<rt>žõdį</rt></ruby>");
+/// ```
+pub fn to_ruby_html(
+    word: &str,
+    case: Case,
+    options: &RubyOptions,
+) -> Result<String, PhonologyError> {
+    to_ruby_html_with(&PhonologyEngine, word, case, options)
+}
+
+/// Like [`to_ruby_html`], but gets the stress options from `resolver` instead of the engine.
+pub fn to_ruby_html_with(
+    resolver: &impl StressResolver,
+    word: &str,
+    case: Case,
+    options: &RubyOptions,
+) -> Result<String, PhonologyError> {
+    let stress_options = resolver.stress_options(word)?;
+    if stress_options.is_empty() {
+        return Ok(text::escape_xml(word));
+    }
+    let (option, _) = select_option(
+        word,
+        &stress_options,
+        Some(case.lithuanian_name()),
+        None,
+        &Default::default(),
+    )?;
+    let accented = try_stress_with_option(word, option)?;
+    let (base, annotation) = if options.accented_base {
+        (accented.as_str(), word)
+    } else {
+        (word, accented.as_str())
+    };
+    Ok(format!(
+        "<ruby>{}<rt>{}</rt></ruby>",
+        text::escape_xml(base),
+        text::escape_xml(annotation)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StressOption;
+
+    fn resolver(word: &str) -> Result<Vec<StressOption>, PhonologyError> {
+        Ok(match word {
+            "gera" | "<gera>" => vec![StressOption {
+                stress_type: 0,
+                stressed_letter_index: if word == "gera" { 3 } else { 4 },
+                grammatical_case: Some(String::from("Vardininkas")),
+                ..Default::default()
+            }],
+            _ => Vec::new(),
+        })
+    }
+
+    #[test]
+    fn base_and_annotation() {
+        let ruby = |word, options| to_ruby_html_with(&resolver, word, Case::Nominative, &options);
+        assert_eq!(
+            ruby("gera", RubyOptions::default()).unwrap(),
+            "<ruby>gera<rt>gerà</rt></ruby>"
+        );
+        let accented_base = RubyOptions {
+            accented_base: true,
+        };
+        assert_eq!(
+            ruby("gera", accented_base).unwrap(),
+            "<ruby>gerà<rt>gera</rt></ruby>"
+        );
+        assert_eq!(
+            ruby("<gera>", RubyOptions::default()).unwrap(),
+            "<ruby>&lt;gera&gt;<rt>&lt;gerà&gt;</rt></ruby>"
+        );
+    }
+
+    #[test]
+    fn unstressed_words_are_plain_text() {
+        let ruby = |word, case| to_ruby_html_with(&resolver, word, case, &Default::default());
+        assert_eq!(ruby("R&D", Case::Nominative).unwrap(), "R&amp;D");
+        assert!(matches!(
+            ruby("gera", Case::Genitive),
+            Err(PhonologyError::CaseNotFound { .. })
+        ));
+    }
+}
@@ -0,0 +1,163 @@
+use std::path::Path;
+
+use rusqlite::{params, Connection};
+
+use crate::{PhonologyEngine, PhonologyError, StressOption, StressResolver};
+
+/// A [`StressResolver`] that keeps every word's stress options in an SQLite database, so they
+/// survive between runs. Words that aren't in the database yet are looked up with another
+/// resolver, [`PhonologyEngine`] by default, and saved.
+///
+/// Options are stored with their case, number and gender, but not their
+/// [`StressOption::details`]. Words the engine has no options for aren't saved, so they're looked
+/// up again every time.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::{SqliteStressCache, StressResolver};
+///
+/// let cache = SqliteStressCache::open("stress.sqlite3").unwrap();
+/// let options = cache.stress_options("žodį").unwrap();
+/// ```
+pub struct SqliteStressCache<R = PhonologyEngine> {
+    connection: Connection,
+    resolver: R,
+}
+
+impl SqliteStressCache {
+    /// Opens or creates the database at `path`, looking up missing words with the engine.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, PhonologyError> {
+        let connection = Connection::open(path).map_err(|e| cache_error(None, e))?;
+        Self::new(connection, PhonologyEngine)
+    }
+}
+
+impl<R: StressResolver> SqliteStressCache<R> {
+    /// Uses an already open database, looking up missing words with `resolver`. The table is
+    /// created if it isn't there yet.
+    pub fn new(connection: Connection, resolver: R) -> Result<Self, PhonologyError> {
+        connection
+            .execute_batch(
+                "CREATE TABLE IF NOT EXISTS stress_options (
+                    word TEXT NOT NULL,
+                    position INTEGER NOT NULL,
+                    grammatical_case TEXT,
+                    number TEXT,
+                    gender TEXT,
+                    stress_type INTEGER NOT NULL,
+                    stressed_letter_index INTEGER NOT NULL,
+                    PRIMARY KEY (word, position)
+                )",
+            )
+            .map_err(|e| cache_error(None, e))?;
+        Ok(Self {
+            connection,
+            resolver,
+        })
+    }
+
+    fn cached(&self, word: &str) -> rusqlite::Result<Vec<StressOption>> {
+        let mut statement = self.connection.prepare_cached(
+            "SELECT grammatical_case, number, gender, stress_type, stressed_letter_index
+            FROM stress_options WHERE word = ?1 ORDER BY position",
+        )?;
+        let options = statement.query_map([word], |row| {
+            Ok(StressOption {
+                grammatical_case: row.get(0)?,
+                number: row.get(1)?,
+                gender: row.get(2)?,
+                stress_type: row.get(3)?,
+                stressed_letter_index: row.get(4)?,
+                details: Default::default(),
+            })
+        })?;
+        options.collect()
+    }
+
+    fn save(&self, word: &str, options: &[StressOption]) -> rusqlite::Result<()> {
+        let transaction = self.connection.unchecked_transaction()?;
+        {
+            let mut statement = transaction.prepare_cached(
+                "INSERT OR REPLACE INTO stress_options VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            )?;
+            for (position, option) in options.iter().enumerate() {
+                statement.execute(params![
+                    word,
+                    position,
+                    option.grammatical_case,
+                    option.number,
+                    option.gender,
+                    option.stress_type,
+                    option.stressed_letter_index,
+                ])?;
+            }
+        }
+        transaction.commit()
+    }
+}
+
+impl<R: StressResolver> StressResolver for SqliteStressCache<R> {
+    fn stress_options(&self, word: &str) -> Result<Vec<StressOption>, PhonologyError> {
+        let cached = self.cached(word).map_err(|e| cache_error(Some(word), e))?;
+        if !cached.is_empty() {
+            return Ok(cached);
+        }
+        let options = self.resolver.stress_options(word)?;
+        self.save(word, &options)
+            .map_err(|e| cache_error(Some(word), e))?;
+        Ok(options)
+    }
+}
+
+fn cache_error(word: Option<&str>, error: rusqlite::Error) -> PhonologyError {
+    PhonologyError::Cache {
+        word: word.map(str::to_string),
+        message: error.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    #[test]
+    fn looks_up_each_word_once() {
+        let lookups = Cell::new(0);
+        let resolver = |word: &str| {
+            lookups.set(lookups.get() + 1);
+            Ok(match word {
+                "žodį" => vec![
+                    StressOption {
+                        stress_type: 2,
+                        stressed_letter_index: 1,
+                        grammatical_case: Some(String::from("Galininkas")),
+                        number: Some(String::from("vienaskaita")),
+                        ..Default::default()
+                    },
+                    StressOption {
+                        stress_type: 0,
+                        stressed_letter_index: 3,
+                        ..Default::default()
+                    },
+                ],
+                _ => Vec::new(),
+            })
+        };
+        let cache =
+            SqliteStressCache::new(Connection::open_in_memory().unwrap(), resolver).unwrap();
+
+        let first = cache.stress_options("žodį").unwrap();
+        assert_eq!(cache.stress_options("žodį").unwrap(), first);
+        assert_eq!(first.len(), 2);
+        assert_eq!(first[1].stressed_letter_index, 3);
+        assert_eq!(lookups.get(), 1);
+
+        // nothing to save, so it's looked up again
+        cache.stress_options("ir").unwrap();
+        cache.stress_options("ir").unwrap();
+        assert_eq!(lookups.get(), 3);
+    }
+}
@@ -0,0 +1,128 @@
+use pyo3::Python;
+
+use crate::{
+    effective_index, get_stress_options_py, normalize_stress_at, select_option, syllables, text,
+    try_stress_with_option, Case, PhonologyError, StressOption,
+};
+
+const LT_NAMESPACE: &str = "https://github.com/BrewingWeasel/lithuanian-phonology";
+
+/// Turns text into an SSML 1.1 document for a speech synthesizer, with every word wrapped in a
+/// `<w>` element and written with its accent marks.
+///
+/// Each `<w>` carries two attributes in a namespace of the crate's own (bound to the `lt`
+/// prefix), since SSML has no standard way to describe Lithuanian stress:
+///
+/// - `lt:stress` is `short`, `acute` or `circumflex` (see [`crate::normalize_stress_type`])
+/// - `lt:syllable` is which syllable is stressed, counting from 0
+///
+/// Synthesizers are expected to ignore attributes they don't understand, so ones that don't know
+/// about them still read the accented text. Words are stressed for `case` when they have a form in
+/// it, and with the engine's first option otherwise, since the text will usually have words in
+/// other cases (and words like verbs that don't decline at all). Words the engine has no stress
+/// for at all, or whose stress mark can't be placed, are wrapped in `<w>` without the attributes,
+/// and everything between words is copied over as it is, escaped.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::{to_ssml, Case};
+///
+/// let ssml = to_ssml("Žodį!", Case::Accusative).unwrap();
+/// assert!(ssml.contains(r#"<w lt:stress="circumflex" lt:syllable="0">Žõdį</w>!"#));
+/// ```
+pub fn to_ssml(text: &str, case: Case) -> Result<String, PhonologyError> {
+    let mut ssml = format!(
+        r#"<speak version="1.1" xmlns="http://www.w3.org/2001/10/synthesis" xmlns:lt="{LT_NAMESPACE}" xml:lang="lt">"#
+    );
+    Python::with_gil(|py| {
+        for token in text::tokenize(text) {
+            if !token.is_word {
+                ssml.push_str(&text::escape_xml(token.text));
+                continue;
+            }
+            let word = token.text;
+            let options = get_stress_options_py(py, word)?;
+            let option = select_option(
+                word,
+                &options,
+                Some(case.lithuanian_name()),
+                None,
+                &Default::default(),
+            )
+            .map(|(option, _)| option)
+            .ok()
+            .or(options.first());
+            match option.and_then(|option| stressed_word_element(word, option)) {
+                Some(element) => ssml.push_str(&element),
+                None => {
+                    ssml.push_str("<w>");
+                    ssml.push_str(&text::escape_xml(word));
+                    ssml.push_str("</w>");
+                }
+            }
+        }
+        Ok::<_, PhonologyError>(())
+    })?;
+    ssml.push_str("</speak>");
+    Ok(ssml)
+}
+
+// `None` if the stress mark can't be placed, so the attributes never claim a stress the text
+// doesn't have.
+fn stressed_word_element(word: &str, option: &StressOption) -> Option<String> {
+    let accented = match try_stress_with_option(word, option) {
+        Ok(accented) => accented,
+        Err(error) => {
+            log::warn!("{error}, leaving it unstressed");
+            return None;
+        }
+    };
+    let index = effective_index(word, option);
+    let mut attributes = String::new();
+    if let Some(stress) = normalize_stress_at(word, index, option.stress_type) {
+        attributes.push_str(&format!(r#" lt:stress="{}""#, stress.english_name()));
+    }
+    if let Some(syllable) = syllables::nucleus_ordinal(word, index) {
+        attributes.push_str(&format!(r#" lt:syllable="{syllable}""#));
+    }
+    Some(format!(
+        "<w{attributes}>{}</w>",
+        text::escape_xml(&accented)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn word_element_attributes() {
+        let option = StressOption {
+            stress_type: 2,
+            stressed_letter_index: 1,
+            ..Default::default()
+        };
+        assert_eq!(
+            stressed_word_element("Žodį", &option).as_deref(),
+            Some(r#"<w lt:stress="circumflex" lt:syllable="0">Žõdį</w>"#)
+        );
+
+        let option = StressOption {
+            stress_type: 0,
+            stressed_letter_index: 3,
+            ..Default::default()
+        };
+        assert_eq!(
+            stressed_word_element("gera", &option).as_deref(),
+            Some(r#"<w lt:stress="short" lt:syllable="1">gerà</w>"#)
+        );
+
+        let option = StressOption {
+            stress_type: 0,
+            stressed_letter_index: 9,
+            ..Default::default()
+        };
+        assert_eq!(stressed_word_element("gera", &option), None);
+    }
+}
@@ -0,0 +1,278 @@
+use crate::{
+    accentuate_batch, accentuate_with, effective_index, syllabify, text, AccentuationOptions, Case,
+    PhonologyEngine, PhonologyError, StressResolver, StressedWord,
+};
+
+/// How many words of a corpus take each stress type. See [`stress_type_histogram`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StressTypeHistogram {
+    /// Counts for stress types 0 (short), 1 (acute) and 2 (circumflex).
+    pub counts: [usize; 3],
+    /// Words that couldn't be stressed in the case, or had a stress type the crate doesn't know.
+    pub failed: usize,
+}
+
+impl StressTypeHistogram {
+    fn add(&mut self, stressed: &Result<StressedWord, PhonologyError>) {
+        match stressed
+            .as_ref()
+            .ok()
+            .and_then(|s| self.counts.get_mut(s.option.stress_type as usize))
+        {
+            Some(count) => *count += 1,
+            None => self.failed += 1,
+        }
+    }
+}
+
+/// Counts how many of `words` take each stress type in `case`. Every word is counted, repeats
+/// included, so this describes the text rather than its vocabulary.
+///
+/// Words without a form in the case are counted in [`StressTypeHistogram::failed`]. The only error
+/// is [`PhonologyError::Engine`], since if the engine couldn't be called there's nothing to count.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::{stress_type_histogram, Case};
+///
+/// let histogram = stress_type_histogram(&["žodį", "namą", "kelią"], Case::Accusative).unwrap();
+/// println!("{} circumflexes", histogram.counts[2]);
+/// ```
+pub fn stress_type_histogram(
+    words: &[&str],
+    case: Case,
+) -> Result<StressTypeHistogram, PhonologyError> {
+    let batch = accentuate_batch(
+        words,
+        Some(case.lithuanian_name()),
+        None,
+        &AccentuationOptions::default(),
+    );
+    let mut histogram = StressTypeHistogram::default();
+    for (_, stressed) in &batch.items {
+        if let Err(error @ PhonologyError::Engine { .. }) = stressed {
+            return Err(error.clone());
+        }
+        histogram.add(stressed);
+    }
+    Ok(histogram)
+}
+
+/// Sums up how a word can be stressed: every distinct pair of a stress type and the letter that
+/// carries it, across all of its readings, in the order the engine first gives them. Readings
+/// that put the same stress on the same letter, like the genitive singular and the nominative
+/// plural of many nouns, become one pair, so a word with fixed stress has a single pair and a
+/// mobile one several.
+///
+/// The letter is the one the mark is written on when the word is accentuated, in lowercase, so
+/// `galvos` gives `(2, 'o')` for `galvõs` and `(1, 'a')` for `gálvos`. Letters are compared as
+/// letters, not positions, so the rare word with the same stress on two different `a`s has one pair
+/// for both.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::distinct_stress_patterns;
+///
+/// assert_eq!(distinct_stress_patterns("galvos").unwrap(), [(2, 'o'), (1, 'a')]);
+/// ```
+pub fn distinct_stress_patterns(word: &str) -> Result<Vec<(u8, char)>, PhonologyError> {
+    distinct_stress_patterns_with(&PhonologyEngine, word)
+}
+
+/// Like [`distinct_stress_patterns`], but gets the stress options from `resolver` instead of the
+/// engine.
+pub fn distinct_stress_patterns_with(
+    resolver: &impl StressResolver,
+    word: &str,
+) -> Result<Vec<(u8, char)>, PhonologyError> {
+    let mut patterns = Vec::new();
+    for option in resolver.stress_options(word)? {
+        let Some(letter) = word.chars().nth(effective_index(word, &option)) else {
+            continue;
+        };
+        let pattern = (
+            option.stress_type,
+            letter.to_lowercase().next().unwrap_or(letter),
+        );
+        if !patterns.contains(&pattern) {
+            patterns.push(pattern);
+        }
+    }
+    Ok(patterns)
+}
+
+/// How hard a text is to read aloud, as returned by [`text_report`].
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextReport {
+    /// How many words the text has, repeats included.
+    pub words: usize,
+    /// How many syllables all the words have together (see [`syllabify`]).
+    pub syllables: usize,
+    /// How many of the words take each stress type in the case. Words the engine couldn't stress
+    /// in it are counted in [`StressTypeHistogram::failed`].
+    pub stress_types: StressTypeHistogram,
+    /// `syllables` divided by `words`, or 0 for a text without words.
+    pub average_syllables_per_word: f64,
+}
+
+/// Sums up a text for judging how hard it is: how many words and syllables it has, how its words
+/// are stressed in `case`, and how many of them the engine couldn't stress. Words are found as in
+/// [`crate::annotate_sentence`], and every word counts, repeats included, like in
+/// [`stress_type_histogram`].
+///
+/// Syllables are counted for every word, including the ones that couldn't be stressed. The only
+/// error is [`PhonologyError::Engine`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::{text_report, Case};
+///
+/// let report = text_report("Mama skaito knygą.", Case::Nominative).unwrap();
+/// println!("{} syllables per word", report.average_syllables_per_word);
+/// ```
+pub fn text_report(text: &str, case: Case) -> Result<TextReport, PhonologyError> {
+    text_report_with(&PhonologyEngine, text, case)
+}
+
+/// Like [`text_report`], but gets the stress options from `resolver` instead of the engine.
+pub fn text_report_with(
+    resolver: &impl StressResolver,
+    text: &str,
+    case: Case,
+) -> Result<TextReport, PhonologyError> {
+    let mut report = TextReport::default();
+    for token in text::tokenize(text).iter().filter(|token| token.is_word) {
+        let stressed = accentuate_with(
+            resolver,
+            token.text,
+            Some(case.lithuanian_name()),
+            None,
+            &AccentuationOptions::default(),
+        );
+        if let Err(error @ PhonologyError::Engine { .. }) = stressed {
+            return Err(error);
+        }
+        report.stress_types.add(&stressed);
+        report.words += 1;
+        report.syllables += syllabify(token.text).len();
+    }
+    if report.words > 0 {
+        report.average_syllables_per_word = report.syllables as f64 / report.words as f64;
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StressOption;
+
+    #[test]
+    fn tallies_stress_types_and_failures() {
+        let stressed = |stress_type| {
+            Ok(StressedWord {
+                accented: String::from("ar"),
+                mark: String::new(),
+                option: StressOption {
+                    stress_type,
+                    ..Default::default()
+                },
+                fell_back: false,
+                case: None,
+                confident: true,
+            })
+        };
+        let mut histogram = StressTypeHistogram::default();
+        for result in [
+            stressed(2),
+            stressed(0),
+            stressed(2),
+            stressed(7),
+            Err(PhonologyError::NoVocative {
+                word: String::from("ar"),
+            }),
+        ] {
+            histogram.add(&result);
+        }
+        assert_eq!(
+            histogram,
+            StressTypeHistogram {
+                counts: [1, 0, 2],
+                failed: 2
+            }
+        );
+    }
+
+    #[test]
+    fn patterns_are_deduplicated() {
+        let option = |stress_type, stressed_letter_index| StressOption {
+            stress_type,
+            stressed_letter_index,
+            ..Default::default()
+        };
+        let resolver = |_: &str| {
+            Ok(vec![
+                option(2, 4),
+                option(1, 1),
+                option(2, 4),
+                option(1, 1),
+                option(0, 4),
+            ])
+        };
+        assert_eq!(
+            distinct_stress_patterns_with(&resolver, "Galvos").unwrap(),
+            [(2, 'o'), (1, 'a'), (0, 'o')]
+        );
+    }
+
+    #[test]
+    fn reports_a_text() {
+        let resolver = |word: &str| -> Result<Vec<StressOption>, PhonologyError> {
+            let (stress_type, stressed_letter_index) = match word.to_lowercase().as_str() {
+                "mama" => (1, 1),
+                "skaito" => (1, 3),
+                "knygą" => (1, 2),
+                "ką" => {
+                    return Err(PhonologyError::Engine {
+                        word: word.to_string(),
+                        message: String::from("no engine"),
+                    })
+                }
+                _ => return Ok(Vec::new()),
+            };
+            Ok(vec![StressOption {
+                stress_type,
+                stressed_letter_index,
+                ..Default::default()
+            }])
+        };
+        let report = text_report_with(
+            &resolver,
+            "Mama skaito knygą, mama — Brno.",
+            Case::Nominative,
+        );
+        assert_eq!(
+            report.unwrap(),
+            TextReport {
+                words: 5,
+                // ma-ma skai-to kny-gą ma-ma Brno
+                syllables: 9,
+                stress_types: StressTypeHistogram {
+                    counts: [0, 4, 0],
+                    failed: 1,
+                },
+                average_syllables_per_word: 1.8,
+            }
+        );
+        assert_eq!(
+            text_report_with(&resolver, " — ", Case::Nominative).unwrap(),
+            TextReport::default()
+        );
+        assert!(text_report_with(&resolver, "Ką skaito?", Case::Nominative).is_err());
+    }
+}
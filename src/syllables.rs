@@ -0,0 +1,572 @@
+use std::sync::OnceLock;
+
+// Every vowel and sonorant, by what it can do. The rest of the crate asks these through
+// `classify`, `vowel_length` and the sonorant functions below rather than listing letters itself.
+const SHORT_VOWELS: [char; 2] = ['i', 'u'];
+const VARIABLE_VOWELS: [char; 2] = ['a', 'e'];
+const LONG_VOWELS: [char; 8] = ['ą', 'ę', 'ė', 'į', 'y', 'o', 'ų', 'ū'];
+const LIQUIDS: [char; 2] = ['l', 'r'];
+const NASALS: [char; 2] = ['m', 'n'];
+const GLIDES: [char; 2] = ['v', 'j'];
+const DIPHTHONGS: [(char, char); 7] = [
+    ('a', 'i'),
+    ('a', 'u'),
+    ('e', 'i'),
+    ('e', 'u'),
+    ('i', 'e'),
+    ('u', 'i'),
+    ('u', 'o'),
+];
+
+/// What kind of sound a letter stands for, as far as syllables and stress are concerned. See
+/// [`classify`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LetterClass {
+    /// `a`, `ą`, `e`, `ę`, `ė`, `i`, `į`, `y`, `o`, `u`, `ų` and `ū`, which make up syllable
+    /// nuclei.
+    Vowel,
+    /// `l`, `m`, `n`, `r`, `v` and `j`. The first four can also be the second half of a mixed
+    /// diphthong like the `il` in `pilnas`, and carry its stress.
+    Sonorant,
+    /// Every other consonant, including the `f`, `h`, `q`, `w` and `x` of loanwords.
+    Obstruent,
+    /// Anything that isn't a Lithuanian letter, like digits, punctuation and other scripts.
+    Other,
+}
+
+/// Which [`LetterClass`] a letter belongs to. Uppercase letters are classified like their
+/// lowercase forms, and letters with stress marks (`ã`, `ù`) like the letter without them.
+///
+/// # Examples
+///
+/// ```
+/// use lithuanian_phonology::{classify, LetterClass};
+///
+/// assert_eq!(classify('ė'), LetterClass::Vowel);
+/// assert_eq!(classify('Ũ'), LetterClass::Vowel);
+/// assert_eq!(classify('r'), LetterClass::Sonorant);
+/// assert_eq!(classify('ž'), LetterClass::Obstruent);
+/// assert_eq!(classify('-'), LetterClass::Other);
+/// ```
+pub fn classify(c: char) -> LetterClass {
+    let Some(letter) = lithuanian_letter(c) else {
+        return LetterClass::Other;
+    };
+    if vowel_length(letter).is_some() {
+        LetterClass::Vowel
+    } else if [LIQUIDS, NASALS, GLIDES]
+        .iter()
+        .any(|set| set.contains(&letter))
+    {
+        LetterClass::Sonorant
+    } else {
+        LetterClass::Obstruent
+    }
+}
+
+// The lowercase Lithuanian letter a character is, once its stress marks are taken off. Only
+// stress marks are: the ogonek of `ą` and the dot of `ė` make other letters.
+fn lithuanian_letter(c: char) -> Option<char> {
+    let lower = c.to_lowercase().next().unwrap_or(c);
+    if crate::ALPHABET.contains(&lower) {
+        return Some(lower);
+    }
+    match crate::strip_accentuation(&lower.to_string())
+        .chars()
+        .collect::<Vec<_>>()[..]
+    {
+        [letter] if crate::ALPHABET.contains(&letter) => Some(letter),
+        _ => None,
+    }
+}
+
+/// How long a vowel is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum VowelLength {
+    /// `i` and `u`, which are always short.
+    Short,
+    /// `a` and `e`, which are short but can be lengthened under stress.
+    Variable,
+    /// `o` (except in some loanwords) and the vowels written with a mark of their own, `ą`, `ę`,
+    /// `ė`, `į`, `y`, `ų` and `ū`.
+    Long,
+}
+
+/// The length of a vowel, classified like [`classify`] does, or `None` for anything else.
+pub(crate) fn vowel_length(c: char) -> Option<VowelLength> {
+    let letter = lithuanian_letter(c)?;
+    if SHORT_VOWELS.contains(&letter) {
+        Some(VowelLength::Short)
+    } else if VARIABLE_VOWELS.contains(&letter) {
+        Some(VowelLength::Variable)
+    } else if LONG_VOWELS.contains(&letter) {
+        Some(VowelLength::Long)
+    } else {
+        None
+    }
+}
+
+/// Whether a letter is one of the sonorants that can end a mixed diphthong, `l`, `m`, `n` and
+/// `r`.
+pub(crate) fn is_diphthong_sonorant(c: char) -> bool {
+    lithuanian_letter(c).is_some_and(|letter| LIQUIDS.contains(&letter) || NASALS.contains(&letter))
+}
+
+/// Whether a letter is one of the sonorants that can follow another consonant at the start of a
+/// syllable, `l`, `r`, `v` and `j`.
+pub(crate) fn is_onset_sonorant(c: char) -> bool {
+    lithuanian_letter(c).is_some_and(|letter| LIQUIDS.contains(&letter) || GLIDES.contains(&letter))
+}
+
+/// The sounds [`PhonologyConfig::syllabify`], [`PhonologyConfig::nucleus_count`] and
+/// [`PhonologyConfig::accentuate_forced`] find syllable nuclei with, for users who need to split
+/// words by a different analysis than the standard Lithuanian one, like a dialect's or a
+/// textbook's. [`PhonologyConfig::default`] is the standard inventory that [`crate::syllabify`],
+/// [`crate::nucleus_count`] and [`crate::accentuate_forced`] use.
+///
+/// Letters are compared in lowercase and without stress marks, so every set should be written in
+/// lowercase letters without them.
+///
+/// Only these methods use the config. Everything that starts from the engine's stress options,
+/// like [`crate::accentuate`], [`crate::analyze`] or [`crate::pronunciation`], always uses the
+/// standard inventory, since the engine's options follow it: that is how the crate finds the
+/// nucleus an option stresses, and moves a mark the engine put on an `i` that only softens the
+/// consonant before it, or on a sonorant outside of a mixed diphthong, onto the right vowel.
+///
+/// # Examples
+///
+/// ```
+/// use lithuanian_phonology::PhonologyConfig;
+///
+/// // read "uo" as two vowels instead of a diphthong
+/// let mut config = PhonologyConfig::default();
+/// config.diphthongs.retain(|&pair| pair != ('u', 'o'));
+/// assert_eq!(config.syllabify("duona"), ["du", "o", "na"]);
+/// assert_eq!(config.nucleus_count("duona"), 3);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PhonologyConfig {
+    /// The letters that can make up a nucleus on their own. By default the twelve Lithuanian
+    /// vowels, `a`, `ą`, `e`, `ę`, `ė`, `i`, `į`, `y`, `o`, `u`, `ų` and `ū`.
+    pub vowels: Vec<char>,
+    /// The pairs of vowels that are one nucleus, like `ai` or `uo`: by default `ai`, `au`, `ei`,
+    /// `eu`, `ie`, `ui` and `uo`. A pair ending in `i` before another vowel isn't one, since that
+    /// `i` softens the next syllable (`nau-jas`, but `dai-na`). An `i` before any vowel it doesn't
+    /// make a diphthong with only softens the consonant before it, as in `ke-lias`, so listing a
+    /// pair starting with `i` makes it a nucleus instead.
+    pub diphthongs: Vec<(char, char)>,
+    /// The vowels that can start a mixed diphthong, by default the short vowels `a`, `e`, `i` and
+    /// `u`.
+    pub mixed_diphthong_vowels: Vec<char>,
+    /// The sonorants that can end a mixed diphthong, after one of
+    /// [`mixed_diphthong_vowels`](Self::mixed_diphthong_vowels) and before a consonant, like the
+    /// `l` in `pil-nas`. By default `l`, `m`, `n` and `r`.
+    pub mixed_diphthong_sonorants: Vec<char>,
+}
+
+impl Default for PhonologyConfig {
+    fn default() -> Self {
+        Self {
+            vowels: letters(|c| classify(c) == LetterClass::Vowel),
+            diphthongs: DIPHTHONGS.to_vec(),
+            mixed_diphthong_vowels: letters(|c| {
+                vowel_length(c).is_some_and(|length| length != VowelLength::Long)
+            }),
+            mixed_diphthong_sonorants: letters(is_diphthong_sonorant),
+        }
+    }
+}
+
+// The letters of the alphabet that `keep` is true for, in alphabetical order.
+fn letters(keep: impl Fn(char) -> bool) -> Vec<char> {
+    crate::ALPHABET.into_iter().filter(|&c| keep(c)).collect()
+}
+
+impl PhonologyConfig {
+    /// The standard inventory, built once.
+    pub(crate) fn standard() -> &'static Self {
+        static STANDARD: OnceLock<PhonologyConfig> = OnceLock::new();
+        STANDARD.get_or_init(Self::default)
+    }
+
+    /// Like [`crate::syllabify`], but finds the nuclei with these sets.
+    pub fn syllabify(&self, word: &str) -> Vec<String> {
+        let plain = crate::strip_accentuation(word);
+        crate::split_graphemes(word, &crate::syllable_starts(&plain, self))
+    }
+
+    /// Like [`crate::nucleus_count`], but finds the nuclei with these sets.
+    pub fn nucleus_count(&self, word: &str) -> usize {
+        nuclei_in(&crate::strip_accentuation(word), self).len()
+    }
+
+    /// Like [`crate::accentuate_forced`], but finds the nuclei with these sets, so that
+    /// `nucleus_ordinal` counts them and a circumflex goes on the second letter of a diphthong
+    /// from [`diphthongs`](Self::diphthongs).
+    pub fn accentuate_forced(
+        &self,
+        word: &str,
+        nucleus_ordinal: usize,
+        stress_type: u8,
+    ) -> Result<String, crate::PhonologyError> {
+        crate::accentuate_nucleus(word, &nuclei_in(word, self), nucleus_ordinal, stress_type)
+    }
+
+    fn is_vowel(&self, c: char) -> bool {
+        self.vowels.contains(&c)
+    }
+}
+
+pub(crate) fn is_vowel(c: char) -> bool {
+    classify(c) == LetterClass::Vowel
+}
+
+/// What a syllable nucleus is made of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NucleusKind {
+    /// A single vowel.
+    Vowel,
+    /// Two vowels pronounced together, like `ai` or `uo`.
+    Diphthong,
+    /// A short vowel followed by `l`, `m`, `n` or `r` before a consonant, like the `il` in
+    /// `pilnas`.
+    MixedDiphthong,
+}
+
+/// The part of a syllable that can carry stress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Nucleus {
+    /// Index (in chars) of the first letter of the nucleus.
+    pub start: usize,
+    pub kind: NucleusKind,
+}
+
+impl Nucleus {
+    /// How many letters the nucleus is made of.
+    pub fn len(&self) -> usize {
+        match self.kind {
+            NucleusKind::Vowel => 1,
+            NucleusKind::Diphthong | NucleusKind::MixedDiphthong => 2,
+        }
+    }
+
+    /// Whether the letter at `index` is part of the nucleus.
+    pub fn contains(&self, index: usize) -> bool {
+        (self.start..self.start + self.len()).contains(&index)
+    }
+
+    /// Index (in chars) of the letter that carries the mark for a stress type. Circumflexes on
+    /// diphthongs and mixed diphthongs go on the second letter, everything else on the first.
+    pub fn stressed_letter_index(&self, stress_type: u8) -> usize {
+        match (self.kind, stress_type) {
+            (NucleusKind::Diphthong | NucleusKind::MixedDiphthong, 2) => self.start + 1,
+            _ => self.start,
+        }
+    }
+}
+
+fn lowercase_letters(word: &str) -> Vec<char> {
+    word.chars()
+        .map(|c| c.to_lowercase().next().unwrap_or(c))
+        .collect()
+}
+
+/// An `i` before another vowel (other than the `e` of `ie`) only softens the consonant before it,
+/// so it isn't a nucleus of its own and can't carry stress.
+fn is_softening_i(letters: &[char], i: usize, config: &PhonologyConfig) -> bool {
+    letters[i] == 'i'
+        && letters
+            .get(i + 1)
+            .is_some_and(|&next| config.is_vowel(next) && !config.diphthongs.contains(&('i', next)))
+}
+
+/// If the letter at `index` is a softening `i`, returns the index of the vowel after it that
+/// actually carries the stress of that syllable.
+pub(crate) fn past_softening_i(word: &str, index: usize) -> Option<usize> {
+    let letters = lowercase_letters(word);
+    let config = PhonologyConfig::standard();
+    (index < letters.len() && is_softening_i(&letters, index, config)).then_some(index + 1)
+}
+
+/// If the letter at `index` is `l`, `m`, `n` or `r` but isn't the second half of a mixed
+/// diphthong, returns the index of the vowel right before it. Sonorants can only carry stress as
+/// part of a mixed diphthong, after a short vowel and before a consonant, like the `l` in
+/// `pilnas`; anywhere else the stress belongs to the vowel.
+pub(crate) fn off_lone_sonorant(word: &str, index: usize) -> Option<usize> {
+    let letters = lowercase_letters(word);
+    let config = PhonologyConfig::standard();
+    if !letters
+        .get(index)
+        .is_some_and(|c| config.mixed_diphthong_sonorants.contains(c))
+    {
+        return None;
+    }
+    let in_mixed_diphthong = nuclei(word)
+        .iter()
+        .any(|n| n.kind == NucleusKind::MixedDiphthong && n.contains(index));
+    let before = index.checked_sub(1)?;
+    (!in_mixed_diphthong && is_vowel(letters[before])).then_some(before)
+}
+
+/// Finds every syllable nucleus in a word, in order.
+pub(crate) fn nuclei(word: &str) -> Vec<Nucleus> {
+    nuclei_in(word, PhonologyConfig::standard())
+}
+
+fn nuclei_in(word: &str, config: &PhonologyConfig) -> Vec<Nucleus> {
+    let letters = lowercase_letters(word);
+    let followed_by_vowel = |i: usize| letters.get(i + 1).is_some_and(|&c| config.is_vowel(c));
+
+    let mut nuclei = Vec::new();
+    let mut i = 0;
+    while i < letters.len() {
+        let c = letters[i];
+        if !config.is_vowel(c) {
+            i += 1;
+            continue;
+        }
+        if is_softening_i(&letters, i, config) {
+            i += 1;
+            continue;
+        }
+        let next = letters.get(i + 1).copied();
+
+        let kind = match next {
+            Some(n)
+                if config.diphthongs.contains(&(c, n))
+                    && !(n == 'i' && followed_by_vowel(i + 1)) =>
+            {
+                NucleusKind::Diphthong
+            }
+            Some(n)
+                if config.mixed_diphthong_vowels.contains(&c)
+                    && config.mixed_diphthong_sonorants.contains(&n)
+                    && !followed_by_vowel(i + 1) =>
+            {
+                NucleusKind::MixedDiphthong
+            }
+            _ => NucleusKind::Vowel,
+        };
+        let nucleus = Nucleus { start: i, kind };
+        nuclei.push(nucleus);
+        i += nucleus.len();
+    }
+    nuclei
+}
+
+/// Which of a word's nuclei (counting from 0) the letter at `index` belongs to, if any.
+pub(crate) fn nucleus_ordinal(word: &str, index: usize) -> Option<usize> {
+    nuclei(word).iter().position(|n| n.contains(index))
+}
+
+/// Index (in chars) of the first letter of each syllable of a word: 0, and then for each nucleus
+/// after the first the start of its onset, the consonants before it that go with it.
+///
+/// A single consonant between two nuclei starts the second syllable (`ge-ra`), and of several the
+/// last one does (`pil-nas`, `verk-ti`). A consonant followed by `r`, `l`, `v` or `j` goes with
+/// it (`pa-kran-tė`), the digraphs `ch`, `dz` and `dž` count as one consonant, and a softening
+/// `i` goes with the consonant it softens (`ke-lias`). A word without nuclei is one syllable.
+pub(crate) fn syllable_starts(word: &str, config: &PhonologyConfig) -> Vec<usize> {
+    let letters = lowercase_letters(word);
+    if letters.is_empty() {
+        return Vec::new();
+    }
+    let nuclei = nuclei_in(word, config);
+    let mut starts = vec![0];
+    for pair in nuclei.windows(2) {
+        let end = pair[0].start + pair[0].len();
+        let mut start = pair[1].start;
+        if start > end && is_softening_i(&letters, start - 1, config) {
+            start -= 1;
+        }
+        if start > end {
+            start -= 1;
+            let first = letters[start];
+            let before = start
+                .checked_sub(1)
+                .filter(|&i| i >= end)
+                .map(|i| letters[i]);
+            match before {
+                Some('c') if first == 'h' => start -= 1,
+                Some('d') if ['z', 'ž'].contains(&first) => start -= 1,
+                Some(b) if is_onset_sonorant(first) && classify(b) == LetterClass::Obstruent => {
+                    start -= 1
+                }
+                _ => {}
+            }
+        }
+        starts.push(start);
+    }
+    starts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn starts(word: &str) -> Vec<(usize, NucleusKind)> {
+        nuclei(word).iter().map(|n| (n.start, n.kind)).collect()
+    }
+
+    #[test]
+    fn single_vowels() {
+        assert_eq!(
+            starts("gera"),
+            [(1, NucleusKind::Vowel), (3, NucleusKind::Vowel)]
+        );
+        assert_eq!(
+            starts("Ąžuolas"),
+            [
+                (0, NucleusKind::Vowel),
+                (2, NucleusKind::Diphthong),
+                (5, NucleusKind::Vowel)
+            ]
+        );
+    }
+
+    #[test]
+    fn diphthongs_and_softening() {
+        assert_eq!(
+            starts("diena"),
+            [(1, NucleusKind::Diphthong), (4, NucleusKind::Vowel)]
+        );
+        assert_eq!(
+            starts("kelias"),
+            [(1, NucleusKind::Vowel), (4, NucleusKind::Vowel)]
+        );
+        assert_eq!(
+            starts("naujas"),
+            [(1, NucleusKind::Diphthong), (4, NucleusKind::Vowel)]
+        );
+    }
+
+    #[test]
+    fn mixed_diphthongs() {
+        assert_eq!(
+            starts("pilnas"),
+            [(1, NucleusKind::MixedDiphthong), (4, NucleusKind::Vowel)]
+        );
+        assert_eq!(
+            starts("verkti"),
+            [(1, NucleusKind::MixedDiphthong), (5, NucleusKind::Vowel)]
+        );
+    }
+
+    #[test]
+    fn lone_sonorants() {
+        assert_eq!(off_lone_sonorant("pilnas", 2), None);
+        assert_eq!(off_lone_sonorant("gilus", 2), Some(1));
+        assert_eq!(off_lone_sonorant("kėlnės", 2), Some(1));
+        assert_eq!(off_lone_sonorant("mama", 0), None);
+        assert_eq!(off_lone_sonorant("gilus", 1), None);
+    }
+
+    fn syllables(word: &str) -> Vec<String> {
+        let letters: Vec<char> = word.chars().collect();
+        let mut starts = syllable_starts(word, PhonologyConfig::standard());
+        starts.push(letters.len());
+        starts
+            .windows(2)
+            .map(|pair| letters[pair[0]..pair[1]].iter().collect())
+            .collect()
+    }
+
+    #[test]
+    fn syllable_boundaries() {
+        assert_eq!(syllables("gera"), ["ge", "ra"]);
+        assert_eq!(syllables("pilnas"), ["pil", "nas"]);
+        assert_eq!(syllables("verkti"), ["verk", "ti"]);
+        assert_eq!(syllables("kelias"), ["ke", "lias"]);
+        assert_eq!(syllables("Aukštaitija"), ["Aukš", "tai", "ti", "ja"]);
+        assert_eq!(syllables("medžiai"), ["me", "džiai"]);
+        assert_eq!(syllables("obuolys"), ["o", "buo", "lys"]);
+        assert_eq!(syllables("pakrantė"), ["pa", "kran", "tė"]);
+        assert_eq!(syllables("brrr"), ["brrr"]);
+        assert_eq!(syllables(""), Vec::<String>::new());
+    }
+
+    #[test]
+    fn custom_inventory() {
+        let standard = PhonologyConfig::default();
+        assert_eq!(&standard, PhonologyConfig::standard());
+        assert_eq!(standard.syllabify("pilnas"), crate::syllabify("pilnas"));
+
+        // a made up inventory: "ia" is a diphthong, "y" isn't a vowel, and "ėl" is a mixed
+        // diphthong
+        let mut config = PhonologyConfig::default();
+        config.diphthongs.push(('i', 'a'));
+        config.vowels.retain(|&c| c != 'y');
+        config.mixed_diphthong_vowels.push('ė');
+        assert_eq!(config.syllabify("kelias"), ["ke", "lias"]);
+        assert_eq!(config.nucleus_count("kelias"), 2);
+        // pairs are taken from the left, so the "u" of "iau" is left on its own
+        assert_eq!(config.syllabify("kiaušinis"), ["kia", "u", "ši", "nis"]);
+        assert_eq!(config.nucleus_count("lyty"), 0);
+        assert_eq!(config.syllabify("kėlnės"), ["kėl", "nės"]);
+        assert_eq!(
+            nuclei_in("kėlnės", &config)[0].kind,
+            NucleusKind::MixedDiphthong
+        );
+        assert_eq!(nuclei("kėlnės")[0].kind, NucleusKind::Vowel);
+        assert_eq!(config.syllabify("Pilnà"), ["Pil", "nà"]);
+        assert_eq!(config.nucleus_count("Pilnà"), 2);
+        assert_eq!(standard.nucleus_count("gerà"), 2);
+        assert_eq!(standard.nucleus_count("kėlnė̃s"), 2);
+
+        // the stress lands on the nuclei of the config too
+        assert_eq!(config.accentuate_forced("kėlnės", 0, 2).unwrap(), "kėl̃nės");
+        assert_eq!(crate::accentuate_forced("kėlnės", 0, 2).unwrap(), "kė̃lnės");
+        let mut hiatus = PhonologyConfig::default();
+        hiatus.diphthongs.retain(|&pair| pair != ('u', 'o'));
+        assert_eq!(hiatus.accentuate_forced("duona", 1, 2).unwrap(), "duõna");
+        assert!(hiatus.accentuate_forced("duona", 3, 2).is_err());
+    }
+
+    #[test]
+    fn every_letter_is_classified() {
+        let class = |classes: &[LetterClass]| -> String {
+            crate::ALPHABET
+                .iter()
+                .filter(|&&c| classes.contains(&classify(c)))
+                .collect()
+        };
+        assert_eq!(class(&[LetterClass::Vowel]), "aąeęėiįyouųū");
+        assert_eq!(class(&[LetterClass::Sonorant]), "jlmnrv");
+        assert_eq!(class(&[LetterClass::Obstruent]), "bcčdfghkpqsštwxzž");
+        assert_eq!(class(&[LetterClass::Other]), "");
+        for c in crate::ALPHABET {
+            let upper = c.to_uppercase().next().unwrap();
+            assert_eq!(classify(upper), classify(c), "{upper}");
+        }
+        for stressed in ["à", "ã", "ù", "ė̃", "ų́", "l̃"] {
+            let nfc: Vec<char> = stressed.chars().collect();
+            assert_ne!(classify(nfc[0]), LetterClass::Other, "{stressed}");
+        }
+        for other in ['1', '-', ' ', 'ä', 'ж'] {
+            assert_eq!(classify(other), LetterClass::Other, "{other}");
+        }
+
+        let length = |wanted| -> String {
+            crate::ALPHABET
+                .iter()
+                .filter(|&&c| vowel_length(c) == Some(wanted))
+                .collect()
+        };
+        assert_eq!(length(VowelLength::Short), "iu");
+        assert_eq!(length(VowelLength::Variable), "ae");
+        assert_eq!(length(VowelLength::Long), "ąęėįyoųū");
+        assert_eq!(vowel_length('Ū'), Some(VowelLength::Long));
+        assert_eq!(vowel_length('r'), None);
+        let sonorants = |keep: fn(char) -> bool| -> String {
+            crate::ALPHABET.iter().filter(|&&c| keep(c)).collect()
+        };
+        assert_eq!(sonorants(is_diphthong_sonorant), "lmnr");
+        assert_eq!(sonorants(is_onset_sonorant), "jlrv");
+        assert_eq!(
+            PhonologyConfig::default().mixed_diphthong_vowels,
+            ['a', 'e', 'i', 'u']
+        );
+    }
+}
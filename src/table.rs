@@ -0,0 +1,384 @@
+use pyo3::Python;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::{
+    accentuations_by_case, first_option_by_case, get_stress_options_py, try_stress_with_option,
+    Case, PhonologyEngine, PhonologyError, StressOption, StressResolver,
+};
+
+/// Grammatical number. Lithuanian also has a dual, but the engine doesn't use it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Number {
+    Singular,
+    Plural,
+}
+
+impl Number {
+    /// Both numbers, singular first.
+    pub const ALL: [Number; 2] = [Number::Singular, Number::Plural];
+
+    /// The Lithuanian name of the number as the engine uses it, such as `vienaskaita`.
+    pub fn lithuanian_name(self) -> &'static str {
+        match self {
+            Number::Singular => "vienaskaita",
+            Number::Plural => "daugiskaita",
+        }
+    }
+
+    /// Looks up a number by its Lithuanian name, ignoring capitalization.
+    pub fn from_lithuanian_name(name: &str) -> Option<Number> {
+        let name = name.to_lowercase();
+        Number::ALL
+            .into_iter()
+            .find(|number| number.lithuanian_name() == name)
+    }
+}
+
+/// The accented form of a word in each case, or `None` for cases the engine had no form for.
+///
+/// # Examples
+///
+/// ```
+/// use lithuanian_phonology::{Case, DeclensionTable};
+///
+/// let mut table = DeclensionTable::default();
+/// table.set(Case::Accusative, Some(String::from("žõdį")));
+/// assert_eq!(table.get(Case::Accusative), Some("žõdį"));
+/// assert_eq!(table.iter().filter(|(_, form)| form.is_none()).count(), 6);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeclensionTable {
+    pub nominative: Option<String>,
+    pub genitive: Option<String>,
+    pub dative: Option<String>,
+    pub accusative: Option<String>,
+    pub instrumental: Option<String>,
+    pub locative: Option<String>,
+    pub vocative: Option<String>,
+}
+
+impl DeclensionTable {
+    /// The form for a case, if there is one.
+    pub fn get(&self, case: Case) -> Option<&str> {
+        self.slot(case).as_deref()
+    }
+
+    /// Sets the form for a case.
+    pub fn set(&mut self, case: Case, form: Option<String>) {
+        *self.slot_mut(case) = form;
+    }
+
+    /// Goes over every case in declension order, along with its form if there is one.
+    pub fn iter(&self) -> impl Iterator<Item = (Case, Option<&str>)> {
+        Case::ALL.into_iter().map(|case| (case, self.get(case)))
+    }
+
+    fn slot(&self, case: Case) -> &Option<String> {
+        match case {
+            Case::Nominative => &self.nominative,
+            Case::Genitive => &self.genitive,
+            Case::Dative => &self.dative,
+            Case::Accusative => &self.accusative,
+            Case::Instrumental => &self.instrumental,
+            Case::Locative => &self.locative,
+            Case::Vocative => &self.vocative,
+        }
+    }
+
+    fn slot_mut(&mut self, case: Case) -> &mut Option<String> {
+        match case {
+            Case::Nominative => &mut self.nominative,
+            Case::Genitive => &mut self.genitive,
+            Case::Dative => &mut self.dative,
+            Case::Accusative => &mut self.accusative,
+            Case::Instrumental => &mut self.instrumental,
+            Case::Locative => &mut self.locative,
+            Case::Vocative => &mut self.vocative,
+        }
+    }
+}
+
+/// A noun's full declension: a [`DeclensionTable`] for each number.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FullParadigm {
+    pub singular: DeclensionTable,
+    pub plural: DeclensionTable,
+}
+
+impl FullParadigm {
+    /// The table for a number.
+    pub fn table(&self, number: Number) -> &DeclensionTable {
+        match number {
+            Number::Singular => &self.singular,
+            Number::Plural => &self.plural,
+        }
+    }
+
+    /// Goes over every number and case, singular first, along with the form if there is one.
+    pub fn iter(&self) -> impl Iterator<Item = (Number, Case, Option<&str>)> {
+        Number::ALL.into_iter().flat_map(move |number| {
+            self.table(number)
+                .iter()
+                .map(move |(case, form)| (number, case, form))
+        })
+    }
+
+    fn table_mut(&mut self, number: Number) -> &mut DeclensionTable {
+        match number {
+            Number::Singular => &mut self.singular,
+            Number::Plural => &mut self.plural,
+        }
+    }
+}
+
+/// Takes a word and returns it with lithuanian accent marks in every case and number the engine
+/// has an option for, all from a single engine call. Like with [`crate::get_all_accentuations`],
+/// the first option for each case and number wins, and a form it says can't be written is an
+/// error for the whole word. Options that don't say which number they're in are left out.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::{full_paradigm, Case};
+///
+/// let paradigm = full_paradigm("žodis").unwrap();
+/// assert_eq!(paradigm.singular.get(Case::Accusative), Some("žõdį"));
+/// for (number, case, form) in paradigm.iter() {
+///     println!("{number:?} {case:?}: {}", form.unwrap_or("—"));
+/// }
+/// ```
+pub fn full_paradigm(word: &str) -> Result<FullParadigm, PhonologyError> {
+    Python::with_gil(|py| {
+        let mut paradigm = FullParadigm::default();
+        for option in get_stress_options_py(py, word)? {
+            let case = option.grammatical_case.as_deref().and_then(Case::from_name);
+            let number = option
+                .number
+                .as_deref()
+                .and_then(Number::from_lithuanian_name);
+            let (Some(case), Some(number)) = (case, number) else {
+                continue;
+            };
+            let table = paradigm.table_mut(number);
+            if table.get(case).is_none() {
+                table.set(case, Some(try_stress_with_option(word, &option)?));
+            }
+        }
+        Ok(paradigm)
+    })
+}
+
+/// Takes a list of words and returns a [`DeclensionTable`] for each of them, in the order they were
+/// given, holding the GIL for the whole list. Each table is built like
+/// [`crate::get_all_accentuations`], from the first option for each case, so a case that can't be
+/// written is an error for its word. A word that fails doesn't stop the others.
+///
+/// With the `serde` feature the result can be serialized as it is, for exporting to a spreadsheet
+/// or flash cards; errors are written as their messages.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::{build_tables, Case};
+///
+/// for (word, table) in build_tables(&["žodis", "namas"]) {
+///     match table {
+///         Ok(table) => println!("{word}: {}", table.get(Case::Genitive).unwrap_or("—")),
+///         Err(e) => eprintln!("{e}"),
+///     }
+/// }
+/// ```
+pub fn build_tables(words: &[&str]) -> Vec<(String, Result<DeclensionTable, PhonologyError>)> {
+    Python::with_gil(|py| {
+        words
+            .iter()
+            .map(|&word| {
+                let table = get_stress_options_py(py, word)
+                    .and_then(|options| declension_table(word, &options));
+                (word.to_string(), table)
+            })
+            .collect()
+    })
+}
+
+/// Like [`build_tables`], but gets the stress options from `resolver` instead of the engine.
+pub fn build_tables_with(
+    resolver: &impl StressResolver,
+    words: &[&str],
+) -> Vec<(String, Result<DeclensionTable, PhonologyError>)> {
+    words
+        .iter()
+        .map(|&word| {
+            let table = resolver
+                .stress_options(word)
+                .and_then(|options| declension_table(word, &options));
+            (word.to_string(), table)
+        })
+        .collect()
+}
+
+fn declension_table(
+    word: &str,
+    options: &[StressOption],
+) -> Result<DeclensionTable, PhonologyError> {
+    let mut table = DeclensionTable::default();
+    for (case, option) in first_option_by_case(options) {
+        if let Some(case) = Case::from_name(case) {
+            table.set(case, Some(try_stress_with_option(word, option)?));
+        }
+    }
+    Ok(table)
+}
+
+/// Takes a word and returns the pairs of cases it is written the same way in, accented, like the
+/// nominative and the instrumental of `galva`, which are both `galvà`. The forms are the ones
+/// [`crate::get_all_accentuations`] gives, the first reading of each case, and are compared after
+/// NFC normalization, so marks written as combining characters or precomposed letters match.
+///
+/// This compares accented strings, not meanings: two cases that are spelled and stressed the same
+/// are syncretic here even where a grammar would call them different forms, and since each case
+/// only has its first reading, a form shared with another number of the case, like a genitive
+/// singular that is also the nominative plural, only shows up if it's that reading. The cases are
+/// their Lithuanian names, and each pair has the one that comes first in declension order first.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::syncretic_cases;
+///
+/// for (first, second) in syncretic_cases("galva").unwrap() {
+///     println!("{first} = {second}");
+/// }
+/// ```
+pub fn syncretic_cases(word: &str) -> Result<Vec<(String, String)>, PhonologyError> {
+    syncretic_cases_with(&PhonologyEngine, word)
+}
+
+/// Like [`syncretic_cases`], but gets the stress options from `resolver` instead of the engine.
+pub fn syncretic_cases_with(
+    resolver: &impl StressResolver,
+    word: &str,
+) -> Result<Vec<(String, String)>, PhonologyError> {
+    let mut forms = Vec::new();
+    for (case, accented) in accentuations_by_case(word, &resolver.stress_options(word)?) {
+        forms.push((case, accented?.nfc().collect::<String>()));
+    }
+    // cases added with `register_case` come after the seven, in the order the engine gives them
+    forms.sort_by_key(|(case, _)| {
+        Case::from_name(case).map_or(Case::ALL.len(), |case| {
+            Case::ALL
+                .iter()
+                .position(|&c| c == case)
+                .unwrap_or_default()
+        })
+    });
+    let mut pairs = Vec::new();
+    for (i, (case, form)) in forms.iter().enumerate() {
+        for (other, other_form) in &forms[i + 1..] {
+            if form == other_form {
+                pairs.push((case.clone(), other.clone()));
+            }
+        }
+    }
+    Ok(pairs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn option(case: Case, stress_type: u8, stressed_letter_index: usize) -> StressOption {
+        StressOption {
+            stress_type,
+            stressed_letter_index,
+            grammatical_case: Some(case.lithuanian_name().to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn resolver(word: &str) -> Result<Vec<StressOption>, PhonologyError> {
+        match word {
+            "žodis" => Ok(vec![
+                option(Case::Nominative, 2, 1),
+                option(Case::Accusative, 2, 1),
+                option(Case::Accusative, 0, 3),
+            ]),
+            "brrr" => Ok(vec![option(Case::Nominative, 1, 1)]),
+            "klaida" => Err(PhonologyError::Engine {
+                word: word.to_string(),
+                message: String::from("RuntimeError"),
+            }),
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    #[test]
+    fn cases_written_the_same() {
+        let resolver = |word: &str| {
+            Ok(match word {
+                "žodis" => vec![
+                    option(Case::Dative, 2, 1),
+                    option(Case::Nominative, 2, 1),
+                    option(Case::Locative, 0, 3),
+                    option(Case::Accusative, 2, 1),
+                    option(Case::Genitive, 2, 1),
+                    option(Case::Nominative, 0, 3),
+                ],
+                _ => vec![option(Case::Nominative, 1, 1)],
+            })
+        };
+        let pairs = syncretic_cases_with(&resolver, "žodis").unwrap();
+        let pairs: Vec<(&str, &str)> = pairs
+            .iter()
+            .map(|(first, second)| (first.as_str(), second.as_str()))
+            .collect();
+        assert_eq!(
+            pairs,
+            [
+                ("Vardininkas", "Kilmininkas"),
+                ("Vardininkas", "Naudininkas"),
+                ("Vardininkas", "Galininkas"),
+                ("Kilmininkas", "Naudininkas"),
+                ("Kilmininkas", "Galininkas"),
+                ("Naudininkas", "Galininkas"),
+            ]
+        );
+
+        assert!(syncretic_cases_with(&resolver, "brrr").is_err());
+        assert_eq!(syncretic_cases_with(&resolver, "gera").unwrap(), []);
+    }
+
+    #[test]
+    fn a_table_for_each_word() {
+        let tables = build_tables_with(&resolver, &["žodis", "brrr", "klaida", "nežinomas"]);
+        let words: Vec<&str> = tables.iter().map(|(word, _)| word.as_str()).collect();
+        assert_eq!(words, ["žodis", "brrr", "klaida", "nežinomas"]);
+
+        let table = tables[0].1.as_ref().unwrap();
+        assert_eq!(table.get(Case::Nominative), Some("žõdis"));
+        assert_eq!(table.get(Case::Accusative), Some("žõdis"));
+        assert_eq!(table.get(Case::Genitive), None);
+        assert!(matches!(
+            tables[1].1,
+            Err(PhonologyError::UnsupportedStress { letter: 'r', .. })
+        ));
+        assert!(matches!(tables[2].1, Err(PhonologyError::Engine { .. })));
+        assert_eq!(tables[3].1, Ok(DeclensionTable::default()));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn tables_serialize() {
+        let tables = build_tables_with(&resolver, &["žodis", "brrr"]);
+        let json = serde_json::to_value(&tables).unwrap();
+        assert_eq!(json[0][1]["Ok"]["nominative"], "žõdis");
+        assert_eq!(json[0][1]["Ok"]["vocative"], serde_json::Value::Null);
+        assert_eq!(
+            json[1][1]["Err"],
+            "letter 'r' in word 'brrr' can't take stress type 1"
+        );
+    }
+}
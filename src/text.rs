@@ -0,0 +1,88 @@
+use std::ops::Range;
+
+/// A run of letters or a run of everything else in some text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Token<'a> {
+    /// Byte range of the token in the text.
+    pub range: Range<usize>,
+    pub text: &'a str,
+    pub is_word: bool,
+}
+
+/// Splits text into words (runs of letters, including any combining marks on them) and the
+/// punctuation and whitespace between them, keeping every byte of the text.
+pub(crate) fn tokenize(text: &str) -> Vec<Token<'_>> {
+    let mut tokens: Vec<Token> = Vec::new();
+    for (start, c) in text.char_indices() {
+        let is_word = is_word_char(c);
+        let end = start + c.len_utf8();
+        match tokens.last_mut() {
+            Some(last) if last.is_word == is_word => last.range.end = end,
+            _ => tokens.push(Token {
+                range: start..end,
+                text: "",
+                is_word,
+            }),
+        }
+    }
+    for token in &mut tokens {
+        token.text = &text[token.range.clone()];
+    }
+    tokens
+}
+
+/// Whether a character is part of a word: a letter or a combining mark on one.
+pub(crate) fn is_word_char(c: char) -> bool {
+    c.is_alphabetic() || ('\u{0300}'..='\u{036F}').contains(&c)
+}
+
+/// Escapes the characters that can't appear as-is in XML or HTML text and attribute values.
+pub(crate) fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// Every ASCII punctuation character that means something somewhere in Markdown, or in the tables
+// and strikethrough of GitHub's flavor, is escaped with a backslash.
+pub(crate) fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "\\`*_{}[]()<>#+-.!|~&".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn words_and_separators() {
+        let tokens = tokenize("Labas, žõdi!");
+        let parts: Vec<_> = tokens.iter().map(|t| (t.text, t.is_word)).collect();
+        assert_eq!(
+            parts,
+            [("Labas", true), (", ", false), ("žõdi", true), ("!", false)]
+        );
+        assert_eq!(tokens[2].range, 7..13);
+    }
+
+    #[test]
+    fn escapes_markup() {
+        assert_eq!(escape_xml("a < b & \"c\""), "a &lt; b &amp; &quot;c&quot;");
+        assert_eq!(escape_markdown("*a_b* | [c]"), "\\*a\\_b\\* \\| \\[c\\]");
+    }
+}
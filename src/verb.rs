@@ -0,0 +1,150 @@
+use crate::{stressed_word, Mood, PhonologyEngine, PhonologyError, StressResolver};
+
+/// The three principal parts of a verb, accented. Every other form of a Lithuanian verb is built
+/// from the stem of one of them, so together they say how the whole verb is conjugated.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VerbForms {
+    /// The infinitive, like `dìrbti`.
+    pub infinitive: String,
+    /// The third person of the present tense, like `dìrba`.
+    pub present: String,
+    /// The third person of the past tense, like `dìrbo`.
+    pub past: String,
+}
+
+/// Would return the accented principal parts of a verb from its infinitive, but the engine can't
+/// conjugate: it only stresses the forms it's given, the same way it can't decline nouns. So this
+/// is always a [`PhonologyError::NotSupported`] error, and is here so that callers have one place
+/// to find that out. When the other two forms are known, [`accentuate_verb_forms`] stresses all
+/// three.
+///
+/// # Examples
+///
+/// ```
+/// use lithuanian_phonology::{verb_forms, PhonologyError};
+///
+/// assert!(matches!(verb_forms("dirbti"), Err(PhonologyError::NotSupported { .. })));
+/// ```
+pub fn verb_forms(infinitive: &str) -> Result<VerbForms, PhonologyError> {
+    Err(PhonologyError::NotSupported {
+        word: infinitive.to_string(),
+        operation: "conjugate verbs",
+    })
+}
+
+/// Takes the three principal parts of a verb (see [`VerbForms`]) and returns them with lithuanian
+/// accent marks.
+///
+/// The present and past forms are stressed with their first reading in the indicative mood, or
+/// their first reading at all if the engine gives none with a mood, and the infinitive with its
+/// first reading.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::accentuate_verb_forms;
+///
+/// let forms = accentuate_verb_forms("dirbti", "dirba", "dirbo").unwrap();
+/// assert_eq!(forms.past, "dìrbo");
+/// ```
+pub fn accentuate_verb_forms(
+    infinitive: &str,
+    present: &str,
+    past: &str,
+) -> Result<VerbForms, PhonologyError> {
+    accentuate_verb_forms_with(&PhonologyEngine, infinitive, present, past)
+}
+
+/// Like [`accentuate_verb_forms`], but gets the stress options from `resolver` instead of the
+/// engine.
+pub fn accentuate_verb_forms_with(
+    resolver: &impl StressResolver,
+    infinitive: &str,
+    present: &str,
+    past: &str,
+) -> Result<VerbForms, PhonologyError> {
+    Ok(VerbForms {
+        infinitive: principal_part(resolver, infinitive, false)?,
+        present: principal_part(resolver, present, true)?,
+        past: principal_part(resolver, past, true)?,
+    })
+}
+
+fn principal_part(
+    resolver: &impl StressResolver,
+    word: &str,
+    indicative: bool,
+) -> Result<String, PhonologyError> {
+    let options = resolver.stress_options(word)?;
+    let option = indicative
+        .then(|| {
+            options
+                .iter()
+                .find(|option| option.mood() == Some(Mood::Indicative))
+        })
+        .flatten()
+        .or(options.first())
+        .ok_or_else(|| PhonologyError::CaseNotFound {
+            word: word.to_string(),
+            case: None,
+            number: None,
+        })?;
+    Ok(stressed_word(word, option.clone(), false, &Default::default())?.accented)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StressOption;
+
+    fn option(mood: Option<&str>, stress_type: u8, stressed_letter_index: usize) -> StressOption {
+        StressOption {
+            stress_type,
+            stressed_letter_index,
+            details: mood
+                .map(|mood| (String::from("mood"), String::from(mood)))
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn resolver(word: &str) -> Result<Vec<StressOption>, PhonologyError> {
+        Ok(match word {
+            "dirbti" => vec![option(None, 0, 1)],
+            // a made up homograph, to check the indicative is preferred
+            "dirba" => vec![
+                option(Some("liepiamoji"), 0, 4),
+                option(Some("tiesioginė"), 0, 1),
+            ],
+            "dirbo" => vec![option(None, 0, 1)],
+            _ => Vec::new(),
+        })
+    }
+
+    #[test]
+    fn principal_parts() {
+        let forms = accentuate_verb_forms_with(&resolver, "dirbti", "dirba", "dirbo").unwrap();
+        assert_eq!(
+            forms,
+            VerbForms {
+                infinitive: String::from("dìrbti"),
+                present: String::from("dìrba"),
+                past: String::from("dìrbo"),
+            }
+        );
+        assert!(matches!(
+            accentuate_verb_forms_with(&resolver, "dirbti", "dirba", "brrr"),
+            Err(PhonologyError::CaseNotFound { word, .. }) if word == "brrr"
+        ));
+    }
+
+    #[test]
+    fn conjugating_is_not_supported() {
+        assert_eq!(
+            verb_forms("dirbti").unwrap_err().to_string(),
+            "phonology_engine can't conjugate verbs (word 'dirbti')"
+        );
+    }
+}
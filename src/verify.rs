@@ -0,0 +1,216 @@
+use unicode_normalization::UnicodeNormalization;
+
+use crate::{accentuate_with, Case, PhonologyEngine, PhonologyError, StressResolver};
+
+/// Whether an accentuation matched the expected form. See [`verify`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Verification {
+    Match,
+    /// The accentuation was different. `actual` is what the crate came up with.
+    Mismatch {
+        actual: String,
+    },
+}
+
+impl Verification {
+    pub fn is_match(&self) -> bool {
+        matches!(self, Verification::Match)
+    }
+}
+
+/// Accentuates a word in a case and checks it against the form it's expected to have, such as one
+/// from a dictionary. Both are NFC normalized first, so it doesn't matter whether the accent
+/// marks are combining characters or precomposed ones like `à`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::{verify, Case, Verification};
+///
+/// assert!(verify("žodį", Case::Accusative, "žõdį").unwrap().is_match());
+/// if let Verification::Mismatch { actual } = verify("žodį", Case::Accusative, "žódį").unwrap() {
+///     println!("got {actual}");
+/// }
+/// ```
+pub fn verify(word: &str, case: Case, expected: &str) -> Result<Verification, PhonologyError> {
+    verify_with(&PhonologyEngine, word, case, expected)
+}
+
+/// Like [`verify`], but gets the stress options from `resolver` instead of the engine.
+pub fn verify_with(
+    resolver: &impl StressResolver,
+    word: &str,
+    case: Case,
+    expected: &str,
+) -> Result<Verification, PhonologyError> {
+    let stressed = accentuate_with(
+        resolver,
+        word,
+        Some(case.lithuanian_name()),
+        None,
+        &Default::default(),
+    )?;
+    let actual: String = stressed.accented.nfc().collect();
+    if actual == expected.nfc().collect::<String>() {
+        Ok(Verification::Match)
+    } else {
+        Ok(Verification::Mismatch { actual })
+    }
+}
+
+/// A claimed accentuation that the crate disagrees with. See [`audit_corpus`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Discrepancy {
+    pub word: String,
+    /// The accented form the corpus claims, as it was given.
+    pub claimed: String,
+    /// What the crate came up with, NFC normalized.
+    pub actual: String,
+}
+
+/// The result of checking a corpus of accented words. See [`audit_corpus`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AuditReport {
+    /// How many pairs matched or had a discrepancy, which leaves out the failed ones.
+    pub checked: usize,
+    /// The pairs that didn't match, in corpus order.
+    pub discrepancies: Vec<Discrepancy>,
+    /// The words that couldn't be accentuated in the case, with why, in corpus order.
+    pub failed: Vec<(String, PhonologyError)>,
+}
+
+/// Checks a whole corpus of `(word, claimed accented form)` pairs against the crate's
+/// accentuations in `case`, such as a textbook's accent marks, with [`verify`] for each pair.
+///
+/// Words the engine has no form in the case for are listed in [`AuditReport::failed`] rather than
+/// as discrepancies, since the claim can't be checked either way. The only error is
+/// [`PhonologyError::Engine`], since if the engine couldn't be called nothing can be checked.
+///
+/// # Examples
+///
+/// ```no_run
+/// use lithuanian_phonology::{audit_corpus, Case};
+///
+/// let pairs = [
+///     (String::from("žodį"), String::from("žõdį")),
+///     (String::from("kelią"), String::from("kélią")),
+/// ];
+/// for discrepancy in audit_corpus(&pairs, Case::Accusative).unwrap().discrepancies {
+///     println!("{}: claimed {}, got {}", discrepancy.word, discrepancy.claimed, discrepancy.actual);
+/// }
+/// ```
+pub fn audit_corpus(pairs: &[(String, String)], case: Case) -> Result<AuditReport, PhonologyError> {
+    audit_corpus_with(&PhonologyEngine, pairs, case)
+}
+
+/// Like [`audit_corpus`], but gets the stress options from `resolver` instead of the engine.
+pub fn audit_corpus_with(
+    resolver: &impl StressResolver,
+    pairs: &[(String, String)],
+    case: Case,
+) -> Result<AuditReport, PhonologyError> {
+    let mut report = AuditReport::default();
+    for (word, claimed) in pairs {
+        match verify_with(resolver, word, case, claimed) {
+            Ok(verification) => {
+                report.checked += 1;
+                if let Verification::Mismatch { actual } = verification {
+                    report.discrepancies.push(Discrepancy {
+                        word: word.clone(),
+                        claimed: claimed.clone(),
+                        actual,
+                    });
+                }
+            }
+            Err(error @ PhonologyError::Engine { .. }) => return Err(error),
+            Err(error) => report.failed.push((word.clone(), error)),
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StressOption;
+
+    fn resolver(_: &str) -> Result<Vec<StressOption>, PhonologyError> {
+        Ok(vec![StressOption {
+            stress_type: 0,
+            stressed_letter_index: 3,
+            grammatical_case: Some(String::from("Vardininkas")),
+            ..Default::default()
+        }])
+    }
+
+    #[test]
+    fn combining_and_precomposed_marks_match() {
+        // the stress map writes a combining grave on u
+        for expected in ["katù", "katu\u{0300}"] {
+            assert_eq!(
+                verify_with(&resolver, "katu", Case::Nominative, expected),
+                Ok(Verification::Match)
+            );
+        }
+    }
+
+    #[test]
+    fn mismatch_has_actual_form() {
+        assert_eq!(
+            verify_with(&resolver, "katu", Case::Nominative, "kátu"),
+            Ok(Verification::Mismatch {
+                actual: String::from("katù")
+            })
+        );
+        assert!(verify_with(&resolver, "katu", Case::Genitive, "katù").is_err());
+    }
+
+    #[test]
+    fn audit_lists_discrepancies_and_failures() {
+        let resolver = |word: &str| -> Result<Vec<StressOption>, PhonologyError> {
+            match word {
+                "vanduo" => Err(PhonologyError::InvalidStressIndex {
+                    word: word.to_string(),
+                    index: 9,
+                }),
+                _ => resolver(word),
+            }
+        };
+        let pairs = [
+            ("katu", "katu\u{0300}"),
+            ("katu", "kátu"),
+            ("vanduo", "vanduõ"),
+        ]
+        .map(|(word, claimed)| (word.to_string(), claimed.to_string()));
+        let report = audit_corpus_with(&resolver, &pairs, Case::Nominative).unwrap();
+        assert_eq!(report.checked, 2);
+        assert_eq!(
+            report.discrepancies,
+            [Discrepancy {
+                word: String::from("katu"),
+                claimed: String::from("kátu"),
+                actual: String::from("katù"),
+            }]
+        );
+        assert_eq!(report.failed.len(), 1);
+        assert_eq!(report.failed[0].0, "vanduo");
+    }
+
+    #[test]
+    fn audit_stops_when_the_engine_fails() {
+        let resolver = |word: &str| -> Result<Vec<StressOption>, PhonologyError> {
+            Err(PhonologyError::Engine {
+                word: word.to_string(),
+                message: String::from("No module named 'phonology_engine'"),
+            })
+        };
+        let pairs = [(String::from("katu"), String::from("katù"))];
+        assert!(matches!(
+            audit_corpus_with(&resolver, &pairs, Case::Nominative),
+            Err(PhonologyError::Engine { .. })
+        ));
+    }
+}
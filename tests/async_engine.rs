@@ -0,0 +1,67 @@
+#![cfg(feature = "tokio")]
+
+use lithuanian_phonology::{AsyncEngine, Case, PhonologyError, StressOption};
+use tokio_stream::StreamExt;
+
+fn resolver(word: &str) -> Result<Vec<StressOption>, PhonologyError> {
+    let (stress_type, stressed_letter_index) = match word {
+        "žodį" => (2, 1),
+        "gerą" => (1, 3),
+        "kelią" => (2, 1),
+        _ => return Ok(Vec::new()),
+    };
+    Ok(vec![StressOption {
+        stress_type,
+        stressed_letter_index,
+        grammatical_case: Some(Case::Accusative.lithuanian_name().to_string()),
+        ..Default::default()
+    }])
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn accentuates_from_many_tasks() {
+    let engine = AsyncEngine::with_resolver(resolver);
+    let words = [("žodį", "žõdį"), ("gerą", "gerą́"), ("kelią", "kẽlią")];
+
+    let tasks: Vec<_> = (0..32)
+        .map(|i| {
+            let engine = engine.clone();
+            let (word, accented) = words[i % words.len()];
+            tokio::spawn(async move {
+                let result = engine.accentuate(word, Case::Accusative).await;
+                assert_eq!(result.unwrap(), accented);
+            })
+        })
+        .collect();
+    for task in tasks {
+        task.await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn errors_come_back_to_the_caller() {
+    let engine = AsyncEngine::with_resolver(resolver);
+    let error = engine.accentuate("žodį", Case::Genitive).await.unwrap_err();
+    assert!(matches!(error, PhonologyError::CaseNotFound { .. }));
+    assert!(engine.accentuate("namas", Case::Accusative).await.is_err());
+}
+
+#[tokio::test]
+async fn streams_results_in_order() {
+    let engine = AsyncEngine::with_resolver(resolver);
+    let words = ["kelią", "namas", "žodį", "gerą"];
+    let results: Vec<_> = engine
+        .accentuate_stream(words, Case::Accusative)
+        .map(|(word, accented)| (word, accented.ok()))
+        .collect()
+        .await;
+    assert_eq!(
+        results,
+        [
+            (String::from("kelią"), Some(String::from("kẽlią"))),
+            (String::from("namas"), None),
+            (String::from("žodį"), Some(String::from("žõdį"))),
+            (String::from("gerą"), Some(String::from("gerą́"))),
+        ]
+    );
+}
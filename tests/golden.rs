@@ -0,0 +1,102 @@
+//! Checks the accentuations in `golden.tsv`. With the `python-engine` feature they are checked
+//! against `phonology_engine`, and skipped if it isn't installed; without it, the words that the
+//! offline table (with the `offline` feature) or the suffix guesser cover are checked against them.
+
+use unicode_normalization::UnicodeNormalization;
+
+use lithuanian_phonology::Case;
+
+const GOLDEN: &str = include_str!("golden.tsv");
+
+struct Golden {
+    word: &'static str,
+    case: Option<Case>,
+    expected: &'static str,
+}
+
+fn golden() -> Vec<Golden> {
+    GOLDEN
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(number, line)| {
+            let fields: Vec<&str> = line.split('\t').collect();
+            let [word, case, expected] = fields[..] else {
+                panic!(
+                    "line {} of golden.tsv isn't word, case, expected",
+                    number + 1
+                );
+            };
+            let case = (!case.is_empty()).then(|| {
+                Case::from_name(case)
+                    .unwrap_or_else(|| panic!("'{case}' on line {} isn't a case", number + 1))
+            });
+            Golden {
+                word,
+                case,
+                expected,
+            }
+        })
+        .collect()
+}
+
+fn nfc(word: &str) -> String {
+    word.nfc().collect()
+}
+
+#[cfg(feature = "python-engine")]
+#[test]
+fn engine_matches_golden_file() {
+    if let Err(error) = lithuanian_phonology::check_environment() {
+        eprintln!("skipping the golden file: {error}");
+        return;
+    }
+    for golden in golden() {
+        let case = golden.case.map(Case::lithuanian_name);
+        let accented = lithuanian_phonology::get_accentuation(golden.word, case, None).unwrap();
+        assert_eq!(nfc(&accented), nfc(golden.expected), "{}", golden.word);
+    }
+}
+
+#[cfg(not(feature = "python-engine"))]
+#[test]
+fn offline_matches_golden_file() {
+    use lithuanian_phonology::{
+        accentuate_with, guess_with_confidence, PhonologyError, StressOption,
+    };
+
+    let resolver = |case: Option<Case>| {
+        move |word: &str| -> Result<Vec<StressOption>, PhonologyError> {
+            Ok(match case {
+                Some(case) => guess_with_confidence(word, case)
+                    .map(|(option, _)| option)
+                    .into_iter()
+                    .collect(),
+                #[cfg(feature = "offline")]
+                None => lithuanian_phonology::lookup_offline(word).unwrap_or_default(),
+                #[cfg(not(feature = "offline"))]
+                None => Vec::new(),
+            })
+        }
+    };
+    let mut covered = 0;
+    for golden in golden() {
+        let case = golden.case.map(Case::lithuanian_name);
+        let options = &Default::default();
+        match accentuate_with(&resolver(golden.case), golden.word, case, None, options) {
+            Ok(stressed) => {
+                covered += 1;
+                assert_eq!(
+                    nfc(&stressed.accented),
+                    nfc(golden.expected),
+                    "{}",
+                    golden.word
+                );
+            }
+            // not in the offline table and not built with a suffix the guesser knows
+            Err(PhonologyError::CaseNotFound { .. }) => {}
+            Err(error) => panic!("{error}"),
+        }
+    }
+    assert!(covered > 0);
+}